@@ -1,10 +1,26 @@
 use crate::error::ContractError;
 use crate::handler::{
-    add_whitelist, add_whitelist_by_owner, get_is_valid_name, get_min_registration_duration,
-    get_node_info_from_name, get_nodehash_from_name, get_owner, get_price, get_registrar,
-    get_rent_price, get_token_id_from_name, owner_register, owner_renew, referal_register,
-    register, renew, set_config, set_enable_registration, set_referal_percentage,
-    set_whitelist_price, withdraw,
+    add_price_tier, add_to_blocklist, add_whitelist, add_whitelist_by_owner, batch_register,
+    batch_renew, bid, cancel_auction, claim_referral_rewards, claim_with_proof, commit,
+    deposit_auto_renew, distribute, get_accepted_denoms, get_auction, get_auctions, get_auto_renew,
+    get_auto_renew_config, get_blocklist, get_commitment, get_commitment_timestamp,
+    get_cw20_tokens, get_duration_discounts, get_duration_limits, get_expiry_premium,
+    get_fee_split, get_is_claimed, get_is_valid_name, get_max_commitment_age, get_merkle_campaign,
+    get_min_commitment_age, get_min_registration_duration, get_name_policy,
+    get_node_info_from_name, get_nodehash_from_name, get_owner, get_premium_names, get_price,
+    get_price_source, get_price_tiers, get_promo_code, get_referral_rewards, get_referral_stats,
+    get_referral_tier, get_referral_tiers, get_registrar, get_rent_price, get_token_id_from_name,
+    get_treasury, get_whitelist, migrate_config, owner_register, owner_renew, process_renewals,
+    prune_commitments, receive_cw20, referal_register, register, remove_accepted_denom,
+    remove_cw20_token, remove_from_blocklist, remove_merkle_campaign, remove_premium_name,
+    remove_price_tier, remove_promo_code, remove_whitelist, renew, save_price_tiers,
+    set_accepted_denom, set_auto_renew_config, set_config, set_cw20_token, set_duration_discounts,
+    set_duration_limits, set_enable_registration, set_expiry_premium, set_fee_split,
+    set_merkle_campaign, set_name_policy, set_premium_name, set_price_source, set_promo_code,
+    set_referal_percentage, set_referral_tiers, set_treasury, set_whitelist_price, settle_auction,
+    start_auction, update_price_tier, validate_accepted_denoms, validate_commitment_age,
+    validate_duration_discounts, validate_duration_limits, validate_expiry_premium,
+    validate_fee_split, validate_name_policy, validate_price_source, withdraw, withdraw_auto_renew,
 };
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::state::{Config, CONFIG};
@@ -15,7 +31,7 @@ use sei_cosmwasm::SeiQueryWrapper;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
+    deps: DepsMut<SeiQueryWrapper>,
     _env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
@@ -25,23 +41,49 @@ pub fn instantiate(
         .api
         .addr_canonicalize(msg.reverse_registrar_address.as_str())?;
     let owner = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let treasury = match msg.treasury {
+        Some(treasury) => deps.api.addr_canonicalize(&treasury)?,
+        None => owner.clone(),
+    };
+    validate_commitment_age(msg.min_commitment_age, msg.max_commitment_age)?;
+    let name_policy = msg.name_policy.unwrap_or_default();
+    validate_name_policy(&name_policy)?;
+    let duration_discounts = validate_duration_discounts(msg.duration_discounts)?;
+    validate_duration_limits(
+        msg.min_registration_duration,
+        msg.max_registration_duration,
+        msg.max_expiry_duration,
+    )?;
+    validate_price_source(deps.as_ref(), &msg.price_source)?;
+    validate_accepted_denoms(deps.as_ref(), &msg.accepted_denoms)?;
+    validate_expiry_premium(&msg.expiry_premium)?;
+    validate_fee_split(deps.as_ref(), &msg.fee_split)?;
 
     CONFIG.save(
         deps.storage,
         &Config {
+            max_commitment_age: msg.max_commitment_age,
+            min_commitment_age: msg.min_commitment_age,
             min_registration_duration: msg.min_registration_duration,
-            tier1_price: msg.tier1_price,
-            tier2_price: msg.tier2_price,
-            tier3_price: msg.tier3_price,
+            max_registration_duration: msg.max_registration_duration,
+            max_expiry_duration: msg.max_expiry_duration,
+            duration_discounts,
+            name_policy,
             whitelist_price: msg.whitelist_price,
+            price_source: msg.price_source,
+            accepted_denoms: msg.accepted_denoms,
+            expiry_premium: msg.expiry_premium,
+            fee_split: msg.fee_split,
             referal_percentage: msg.referal_percentage,
             enable_registration: msg.enable_registration,
             registrar_address,
             reverse_registrar_address,
             owner,
+            treasury,
             description: msg.description,
         },
     )?;
+    save_price_tiers(deps.storage, msg.price_tiers)?;
     Ok(Response::new()
         .add_attribute("method", "instantiate")
         .add_attribute("owner", info.sender))
@@ -55,13 +97,16 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
+        ExecuteMsg::Commit { commitment } => commit(deps, env, info, commitment),
         ExecuteMsg::Register {
             name,
             owner,
             duration,
+            secret,
             resolver,
             address,
             reverse_record,
+            promo_code,
         } => register(
             deps,
             env,
@@ -69,14 +114,17 @@ pub fn execute(
             name,
             owner,
             duration,
+            secret,
             resolver,
             address,
             reverse_record,
+            promo_code,
         ),
         ExecuteMsg::ReferalRegister {
             name,
             owner,
             duration,
+            secret,
             resolver,
             address,
             referer,
@@ -88,19 +136,26 @@ pub fn execute(
             name,
             owner,
             duration,
+            secret,
             resolver,
             address,
             referer,
             reverse_record,
         ),
-        ExecuteMsg::Renew { name, duration } => renew(deps, env, info, name, duration),
+        ExecuteMsg::PruneCommitments { start_after, limit } => {
+            prune_commitments(deps, env, info, start_after, limit)
+        }
+        ExecuteMsg::Renew {
+            name,
+            duration,
+            promo_code,
+        } => renew(deps, env, info, name, duration, promo_code),
 
         // Only owner
         ExecuteMsg::SetConfig {
+            max_commitment_age,
+            min_commitment_age,
             min_registration_duration,
-            tier1_price,
-            tier2_price,
-            tier3_price,
             registrar_address,
             reverse_registrar_address,
             owner,
@@ -110,17 +165,20 @@ pub fn execute(
             deps,
             env,
             info,
+            max_commitment_age,
+            min_commitment_age,
             min_registration_duration,
-            tier1_price,
-            tier2_price,
-            tier3_price,
             registrar_address,
             reverse_registrar_address,
             owner,
             enable_registration,
             description,
         ),
-        ExecuteMsg::Withdraw {} => withdraw(deps, env, info),
+        ExecuteMsg::Withdraw {
+            denom,
+            amount,
+            recipient,
+        } => withdraw(deps, env, info, denom, amount, recipient),
         ExecuteMsg::OwnerRegister {
             name,
             owner,
@@ -147,32 +205,224 @@ pub fn execute(
         ExecuteMsg::AddWhiteListByOwner {
             ensname,
             referal_percentage,
-        } => add_whitelist_by_owner(deps, env, info, &ensname, referal_percentage),
+            expires,
+        } => add_whitelist_by_owner(deps, env, info, &ensname, referal_percentage, expires),
+        ExecuteMsg::RemoveWhiteList { ensname } => remove_whitelist(deps, env, info, ensname),
         ExecuteMsg::SetReferalPercentage {
             normal_percentage,
             whitelist_percentage,
         } => set_referal_percentage(deps, env, info, normal_percentage, whitelist_percentage),
         ExecuteMsg::SetWhitelistPrice { price } => set_whitelist_price(deps, env, info, price),
+        ExecuteMsg::SetPriceSource { price_source } => {
+            set_price_source(deps, env, info, price_source)
+        }
+        ExecuteMsg::AddPriceTier {
+            min_length,
+            max_length,
+            annual_price,
+        } => add_price_tier(deps, env, info, min_length, max_length, annual_price),
+        ExecuteMsg::UpdatePriceTier {
+            min_length,
+            max_length,
+            annual_price,
+        } => update_price_tier(deps, env, info, min_length, max_length, annual_price),
+        ExecuteMsg::RemovePriceTier { min_length } => {
+            remove_price_tier(deps, env, info, min_length)
+        }
+        ExecuteMsg::SetPremiumName { name, premium } => {
+            set_premium_name(deps, env, info, name, premium)
+        }
+        ExecuteMsg::SetExpiryPremium { expiry_premium } => {
+            set_expiry_premium(deps, env, info, expiry_premium)
+        }
+        ExecuteMsg::StartAuction {
+            name,
+            reserve_price,
+            min_increment,
+            bidding_period,
+            extension,
+            duration,
+        } => start_auction(
+            deps,
+            env,
+            info,
+            name,
+            reserve_price,
+            min_increment,
+            bidding_period,
+            extension,
+            duration,
+        ),
+        ExecuteMsg::CancelAuction { name } => cancel_auction(deps, env, info, name),
+        ExecuteMsg::Bid { name } => bid(deps, env, info, name),
+        ExecuteMsg::SettleAuction { name } => settle_auction(deps, env, info, name),
+        ExecuteMsg::RemovePremiumName { name } => remove_premium_name(deps, env, info, name),
+        ExecuteMsg::Receive(cw20_msg) => receive_cw20(deps, env, info, cw20_msg),
+        ExecuteMsg::SetCw20Token { token, rate } => set_cw20_token(deps, env, info, token, rate),
+        ExecuteMsg::RemoveCw20Token { token } => remove_cw20_token(deps, env, info, token),
+        ExecuteMsg::SetAcceptedDenom {
+            denom,
+            price_source,
+        } => set_accepted_denom(deps, env, info, denom, price_source),
+        ExecuteMsg::RemoveAcceptedDenom { denom } => remove_accepted_denom(deps, env, info, denom),
+        ExecuteMsg::SetTreasury { treasury } => set_treasury(deps, env, info, treasury),
+        ExecuteMsg::SetFeeSplit { fee_split } => set_fee_split(deps, env, info, fee_split),
+        ExecuteMsg::Distribute {} => distribute(deps, env, info),
+        ExecuteMsg::ClaimReferralRewards {} => claim_referral_rewards(deps, env, info),
+        ExecuteMsg::SetPromoCode {
+            code_hash,
+            promo_code,
+        } => set_promo_code(deps, env, info, code_hash, promo_code),
+        ExecuteMsg::RemovePromoCode { code_hash } => remove_promo_code(deps, env, info, code_hash),
+        ExecuteMsg::SetReferralTiers { tiers } => set_referral_tiers(deps, env, info, tiers),
+        ExecuteMsg::BatchRegister {
+            registrations,
+            skip_unavailable,
+        } => batch_register(deps, env, info, registrations, skip_unavailable),
+        ExecuteMsg::BatchRenew { renewals } => batch_renew(deps, env, info, renewals),
+        ExecuteMsg::DepositAutoRenew { name, duration } => {
+            deposit_auto_renew(deps, env, info, name, duration)
+        }
+        ExecuteMsg::WithdrawAutoRenew { name, amount } => {
+            withdraw_auto_renew(deps, env, info, name, amount)
+        }
+        ExecuteMsg::SetAutoRenewConfig { auto_renew_config } => {
+            set_auto_renew_config(deps, env, info, auto_renew_config)
+        }
+        ExecuteMsg::ProcessRenewals { limit } => process_renewals(deps, env, info, limit),
+        ExecuteMsg::SetDurationDiscounts { duration_discounts } => {
+            set_duration_discounts(deps, env, info, duration_discounts)
+        }
+        ExecuteMsg::SetDurationLimits {
+            max_registration_duration,
+            max_expiry_duration,
+        } => set_duration_limits(
+            deps,
+            env,
+            info,
+            max_registration_duration,
+            max_expiry_duration,
+        ),
+        ExecuteMsg::SetNamePolicy { name_policy } => set_name_policy(deps, env, info, name_policy),
+        ExecuteMsg::AddToBlocklist { entries } => add_to_blocklist(deps, env, info, entries),
+        ExecuteMsg::RemoveFromBlocklist { entries } => {
+            remove_from_blocklist(deps, env, info, entries)
+        }
+        ExecuteMsg::SetMerkleCampaign {
+            campaign,
+            merkle_campaign,
+        } => set_merkle_campaign(deps, env, info, campaign, merkle_campaign),
+        ExecuteMsg::RemoveMerkleCampaign { campaign } => {
+            remove_merkle_campaign(deps, env, info, campaign)
+        }
+        ExecuteMsg::ClaimWithProof {
+            campaign,
+            name,
+            owner,
+            secret,
+            resolver,
+            address,
+            reverse_record,
+            proof,
+        } => claim_with_proof(
+            deps,
+            env,
+            info,
+            campaign,
+            name,
+            owner,
+            secret,
+            resolver,
+            address,
+            reverse_record,
+            proof,
+        ),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps<SeiQueryWrapper>, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps<SeiQueryWrapper>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::RentPrice { name, duration } => to_binary(&get_rent_price(deps, name, duration)?),
+        QueryMsg::RentPrice {
+            name,
+            duration,
+            promo_code,
+        } => to_binary(&get_rent_price(deps, env, name, duration, promo_code)?),
+        QueryMsg::MaxCommitmentAge {} => to_binary(&get_max_commitment_age(deps)?),
+        QueryMsg::MinCommitmentAge {} => to_binary(&get_min_commitment_age(deps)?),
         QueryMsg::MinRegistrationDuration {} => to_binary(&get_min_registration_duration(deps)?),
+        QueryMsg::DurationLimits {} => to_binary(&get_duration_limits(deps)?),
+        QueryMsg::DurationDiscounts {} => to_binary(&get_duration_discounts(deps)?),
         QueryMsg::GetPrice {} => to_binary(&get_price(deps)?),
+        QueryMsg::GetPriceSource {} => to_binary(&get_price_source(deps)?),
+        QueryMsg::PriceTiers {} => to_binary(&get_price_tiers(deps)?),
+        QueryMsg::GetExpiryPremium {} => to_binary(&get_expiry_premium(deps)?),
+        QueryMsg::AcceptedDenoms {} => to_binary(&get_accepted_denoms(deps)?),
+        QueryMsg::Treasury {} => to_binary(&get_treasury(deps)?),
+        QueryMsg::GetFeeSplit {} => to_binary(&get_fee_split(deps)?),
+        QueryMsg::ReferralStats { name } => to_binary(&get_referral_stats(deps, name)?),
+        QueryMsg::ReferralRewards { address } => {
+            to_binary(&get_referral_rewards(deps, address)?)
+        }
+        QueryMsg::PromoCode { code_hash } => to_binary(&get_promo_code(deps, code_hash)?),
+        QueryMsg::MerkleCampaign { campaign } => to_binary(&get_merkle_campaign(deps, campaign)?),
+        QueryMsg::IsClaimed { campaign, address } => {
+            to_binary(&get_is_claimed(deps, campaign, address)?)
+        }
+        QueryMsg::AutoRenew { name } => to_binary(&get_auto_renew(deps, name)?),
+        QueryMsg::GetAutoRenewConfig {} => to_binary(&get_auto_renew_config(deps)?),
+        QueryMsg::ReferralTiers {} => to_binary(&get_referral_tiers(deps)?),
+        QueryMsg::ReferralTier { name } => to_binary(&get_referral_tier(deps, env, name)?),
+        QueryMsg::WhiteList { start_after, limit } => {
+            to_binary(&get_whitelist(deps, start_after, limit)?)
+        }
+        QueryMsg::Cw20Tokens { start_after, limit } => {
+            to_binary(&get_cw20_tokens(deps, start_after, limit)?)
+        }
+        QueryMsg::Auction { name } => to_binary(&get_auction(deps, name)?),
+        QueryMsg::Auctions { start_after, limit } => {
+            to_binary(&get_auctions(deps, start_after, limit)?)
+        }
+        QueryMsg::PremiumNames { start_after, limit } => {
+            to_binary(&get_premium_names(deps, start_after, limit)?)
+        }
+        QueryMsg::Blocklist { start_after, limit } => {
+            to_binary(&get_blocklist(deps, start_after, limit)?)
+        }
         QueryMsg::Registrar {} => to_binary(&get_registrar(deps)?),
         QueryMsg::Owner {} => to_binary(&get_owner(deps)?),
 
-        QueryMsg::IsValidName { name } => to_binary(&get_is_valid_name(&name)?),
+        QueryMsg::IsValidName { name } => to_binary(&get_is_valid_name(deps, &name)?),
+        QueryMsg::NamePolicy {} => to_binary(&get_name_policy(deps)?),
         QueryMsg::GetTokenId { name } => to_binary(&get_token_id_from_name(&name)?),
         QueryMsg::GetNodehash { name } => to_binary(&get_nodehash_from_name(deps, &name)?),
         QueryMsg::GetNodeInfo { name } => to_binary(&get_node_info_from_name(deps, &name)?),
+        QueryMsg::MakeCommitment {
+            name,
+            owner,
+            secret,
+            resolver,
+            address,
+        } => to_binary(&get_commitment(
+            &name, &owner, &secret, &resolver, &address,
+        )?),
+        QueryMsg::CommitmentTimestamp { commitment } => {
+            to_binary(&get_commitment_timestamp(deps, commitment)?)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    msg: MigrateMsg,
+) -> Result<Response, ContractError> {
+    migrate_config(
+        deps,
+        env,
+        msg.max_commitment_age,
+        msg.min_commitment_age,
+        msg.price_source,
+    )
 }
@@ -31,18 +31,98 @@ pub enum ContractError {
     #[error("InvalidName")]
     InvalidName {},
 
-    #[error("NameTooShort")]
-    NameTooShort {},
+    #[error("ReservedName: {name} can only be registered by the owner.")]
+    ReservedName { name: String },
 
-    #[error("DurationTooShort")]
+    #[error("PremiumNotClaimable: {name} is priced above its tier and can't be claimed for free.")]
+    PremiumNotClaimable { name: String },
+
+    #[error("BlockedName: {name} is blocked.")]
+    BlockedName { name: String },
+
+    #[error("InvalidBlocklistEntry: {description}")]
+    InvalidBlocklistEntry { description: String },
+
+    #[error("NameInAuction: {name} can only be acquired through its auction.")]
+    NameInAuction { name: String },
+
+    #[error("AuctionNotFound: There is no auction for {name}.")]
+    AuctionNotFound { name: String },
+
+    #[error("AuctionEnded: The auction ended at {end_time}. Current time is {current}.")]
+    AuctionEnded { end_time: u64, current: u64 },
+
+    #[error("AuctionNotEnded: The auction ends at {end_time}. Current time is {current}.")]
+    AuctionNotEnded { end_time: u64, current: u64 },
+
+    #[error("BidTooLow: The bid is {amount}, but the minimum bid is {min_bid}.")]
+    BidTooLow { amount: Uint128, min_bid: Uint128 },
+
+    #[error("NameTooShort: The name is {length} characters long, but the minimum is {min_length}.")]
+    NameTooShort { length: u32, min_length: u32 },
+
+    #[error("InvalidPromoCode: {description}")]
+    InvalidPromoCode { description: String },
+
+    #[error("CampaignNotFound: There is no campaign {campaign}.")]
+    CampaignNotFound { campaign: String },
+
+    #[error("InvalidMerkleProof: {address} is not in campaign {campaign}.")]
+    InvalidMerkleProof { campaign: String, address: String },
+
+    #[error("AlreadyClaimed: {address} already claimed a name from campaign {campaign}.")]
+    AlreadyClaimed { campaign: String, address: String },
+
+    #[error("NoPriceTier: No price tier covers names of length {length}.")]
+    NoPriceTier { length: u32 },
+
+    #[error("InvalidPriceTier: {description}")]
+    InvalidPriceTier { description: String },
+
+    #[error("InvalidDurationDiscount: {description}")]
+    InvalidDurationDiscount { description: String },
+
+    #[error("InvalidNamePolicy: {description}")]
+    InvalidNamePolicy { description: String },
+
+    #[error("DurationTooShort: The duration is {input_duration}, but the minimum is {min_duration}.")]
     DurationTooShort {
         input_duration: u64,
         min_duration: u64,
     },
 
+    #[error(
+        "DurationTooLong: The duration is {input_duration}, but the maximum is {max_duration}."
+    )]
+    DurationTooLong {
+        input_duration: u64,
+        max_duration: u64,
+    },
+
+    #[error("ExpiryTooFar: The name would expire at {expires}, but expiries are capped at {max_expires}.")]
+    ExpiryTooFar { expires: u64, max_expires: u64 },
+
     #[error("InsufficientFund")]
     InsufficientFund { amount: Uint128, required: Uint128 },
 
+    #[error("InvalidFeeSplit: {description}")]
+    InvalidFeeSplit { description: String },
+
+    #[error("UnexpectedDenom: {denom} is not accepted as payment.")]
+    UnexpectedDenom { denom: String },
+
+    #[error("UnsupportedToken: {token} is not accepted as payment.")]
+    UnsupportedToken { token: String },
+
+    #[error("PriceUnavailable: No exchange rate for {denom}.")]
+    PriceUnavailable { denom: String },
+
+    #[error("StalePrice: The exchange rate was updated at {last_update}, but must be newer than {oldest_allowed}.")]
+    StalePrice { last_update: u64, oldest_allowed: u64 },
+
+    #[error("ReferrerNotFound: {name} does not resolve to an owner.")]
+    ReferrerNotFound { name: String },
+
     #[error("RegistrationDisabled")]
     RegistrationDisabled {},
 
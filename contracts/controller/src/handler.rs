@@ -2,19 +2,43 @@ use std::ops::Mul;
 
 use crate::error::ContractError;
 use crate::msg::{
-    IsValidNameResponse, MinRegistrationDurationResponse, NodeInfoResponse, NodehashResponse,
-    OwnerResponse, PriceResponse, RegistrarResponse, RentPriceResponse, TokenIdResponse,
+    AcceptedDenomsResponse, AuctionResponse, AuctionsResponse, AutoRenewConfigResponse,
+    AutoRenewResponse, BlocklistResponse, CommitmentTimestampResponse, Cw20TokenInfo,
+    Cw20TokensResponse, DurationDiscountsResponse, DurationLimitsResponse, ExpiryPremiumResponse,
+    FeeSplitResponse, IsClaimedResponse, IsValidNameResponse, MakeCommitmentResponse,
+    MaxCommitmentAgeResponse, MerkleCampaignResponse, MinCommitmentAgeResponse,
+    MinRegistrationDurationResponse, NamePolicyResponse, NodeInfoResponse, NodehashResponse,
+    OwnerResponse, PremiumNameInfo, PremiumNamesResponse, PriceFeedQueryMsg, PriceFeedResponse,
+    PriceResponse, PriceSourceResponse, PriceTiersResponse, PromoCodeResponse, ReceiveMsg,
+    ReferralRewardsResponse, ReferralStatsResponse, ReferralTierResponse, ReferralTiersResponse,
+    RegisterRequest, RegistrarResponse, RenewRequest, RentPriceResponse, TokenIdResponse,
+    TreasuryResponse, WhiteListInfo, WhiteListResponse,
+};
+use crate::state::{
+    AcceptedDenom, Auction, AutoRenew, AutoRenewConfig, Bid, BlockKind, BlocklistEntry, Config,
+    Discount, DurationDiscount, ExpiryPremium, FeeRecipient, FeeSplit, MerkleCampaign,
+    PremiumDecay, PremiumName, PriceSource, PriceTier, PromoCode, ReferralTier, WhitelistEntry,
+    AUCTIONS, AUCTION_ESCROW, AUTO_RENEWALS, AUTO_RENEW_CONFIG, AUTO_RENEW_CURSOR,
+    AUTO_RENEW_ESCROW, BLOCKED_LABEL_HASH, BLOCKED_SUBSTRING, BLOCKLIST, COMMITMENTS, CONFIG,
+    CW20_TOKENS, LEGACY_CONFIG, LEGACY_WHITELIST, MERKLE_CAMPAIGNS, MERKLE_CLAIMS, PREMIUM_NAMES,
+    PRICE_TIERS, PROMO_CODES, PROMO_CODE_ADDRESS_USES, PROMO_CODE_USES, REFERRAL_ESCROW,
+    REFERRAL_REWARDS, REFERRAL_STATS, REFERRAL_TIERS, REGISTER_FEE_DENOM, TOTAL_BPS, WHITELIST,
 };
-use crate::state::{CONFIG, REGISTER_FEE_DENOM, WHITELIST};
 use cosmwasm_std::{
-    to_binary, BalanceResponse, BankMsg, BankQuery, Coin, CosmosMsg, Deps, DepsMut, Env,
-    MessageInfo, QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg, WasmQuery, Decimal,
+    from_binary, to_binary, Addr, Attribute, BalanceResponse, BankMsg, BankQuery, Coin, CosmosMsg,
+    Decimal, Deps, DepsMut, Env, MessageInfo, Order, QueryRequest, Response, StdError, StdResult,
+    Storage, Uint128, WasmMsg, WasmQuery,
 };
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw_storage_plus::Bound;
 use hex;
 // use terraswap::asset::{Asset, AssetInfo};
+use dotlabs::name_policy::{validate_label, NamePolicy, NameRule};
+use dotlabs::normalize::normalize_label;
 use dotlabs::registrar::{
     ConfigResponse, ExecuteMsg as RegistrarExecuteMsg, Extension, GetBaseNodeResponse,
-    GetRegistryResponse, IsAvailableResponse, QueryMsg as RegistrarQueryMsg,
+    GetExpiresResponse, GetGracePeriodResponse, GetRegistryResponse, IsAvailableResponse,
+    QueryMsg as RegistrarQueryMsg,
 };
 use dotlabs::registry::QueryMsg as RegistryQueryMsg;
 use dotlabs::registry::{ExecuteMsg as RegistryExecuteMsg, RecordResponse};
@@ -24,6 +48,12 @@ use dotlabs::utils::{get_label_from_name, get_token_id_from_label, keccak256};
 use sei_cosmwasm::{SeiQueryWrapper, ExchangeRatesResponse, SeiQuerier};
 use unicode_segmentation::UnicodeSegmentation;
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+const MAX_BATCH_SIZE: usize = 30;
+const DEFAULT_RENEW_WINDOW: u64 = 30 * 24 * 3600;
+const MAX_KEEPER_TIP_BPS: u32 = 1_000;
+
 fn only_owner(deps: Deps<SeiQueryWrapper>, info: &MessageInfo) -> Result<bool, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let sender = deps.api.addr_canonicalize(info.sender.as_str())?;
@@ -36,44 +66,335 @@ fn only_owner(deps: Deps<SeiQueryWrapper>, info: &MessageInfo) -> Result<bool, C
     Ok(true)
 }
 
-pub fn withdraw(deps: DepsMut<SeiQueryWrapper>, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+pub fn withdraw(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    denom: Option<String>,
+    amount: Option<Uint128>,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
     only_owner(deps.as_ref(), &info)?;
+    let config = CONFIG.load(deps.storage)?;
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => deps.api.addr_humanize(&config.treasury)?,
+    };
+
+    let denom = match denom {
+        Some(denom) => denom,
+        None if amount.is_some() => {
+            return Err(ContractError::BadRequest {
+                msg: String::from("amount can only be set together with denom"),
+            })
+        }
+        None => return withdraw_all(deps.as_ref(), env, &config, recipient),
+    };
+
+    let cw20_token = deps
+        .api
+        .addr_validate(&denom)
+        .ok()
+        .filter(|token| CW20_TOKENS.has(deps.storage, token));
+    let available = match &cw20_token {
+        Some(token) => get_cw20_balance(deps.as_ref(), &env, token)?,
+        None => get_withdrawable_balance(deps.as_ref(), &env, &denom)?,
+    };
+    let amount = amount.unwrap_or(available);
+    if amount > available {
+        return Err(ContractError::InsufficientFund {
+            amount: available,
+            required: amount,
+        });
+    }
+    let message = match &cw20_token {
+        Some(token) => transfer_cw20(token, &recipient, amount)?,
+        None if amount.is_zero() => None,
+        None => Some(CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        })),
+    };
+
+    Ok(Response::new()
+        .add_messages(message)
+        .add_attribute("method", "withdraw")
+        .add_attribute("recipient", recipient)
+        .add_attribute("denom", denom)
+        .add_attribute("amount", amount))
+}
+
+// Sweeps every accepted denom and CW20 token to the recipient
+fn withdraw_all(
+    deps: Deps<SeiQueryWrapper>,
+    env: Env,
+    config: &Config,
+    recipient: Addr,
+) -> Result<Response, ContractError> {
+    let mut messages: Vec<CosmosMsg> = vec![];
+    // Every amount sent, CW20 amounts suffixed by their token address like native coins
+    let mut sent: Vec<String> = vec![];
+    let denoms = std::iter::once(String::from(REGISTER_FEE_DENOM)).chain(
+        config
+            .accepted_denoms
+            .iter()
+            .map(|accepted| accepted.denom.clone()),
+    );
+    for denom in denoms {
+        let balance = get_withdrawable_balance(deps, &env, &denom)?;
+        if !balance.is_zero() {
+            let coin = Coin {
+                denom,
+                amount: balance,
+            };
+            sent.push(coin.to_string());
+            messages.push(CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![coin],
+            }));
+        }
+    }
+
+    let tokens = CW20_TOKENS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for token in tokens {
+        let balance = get_cw20_balance(deps, &env, &token)?;
+        if let Some(transfer_msg) = transfer_cw20(&token, &recipient, balance)? {
+            sent.push(format!("{}{}", balance, token));
+            messages.push(transfer_msg);
+        }
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("method", "withdraw")
+        .add_attribute("recipient", recipient)
+        .add_attribute("coins", sent.join(",")))
+}
 
+fn get_withdrawable_balance(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    denom: &str,
+) -> StdResult<Uint128> {
     let balance_response: BalanceResponse =
         deps.querier.query(&QueryRequest::Bank(BankQuery::Balance {
             address: env.contract.address.to_string(),
-            denom: String::from(REGISTER_FEE_DENOM),
+            denom: denom.to_string(),
         }))?;
+    // Escrowed bids, auto-renew deposits and unclaimed referral rewards are not proceeds
+    let mut escrow = REFERRAL_ESCROW
+        .may_load(deps.storage, denom.to_string())?
+        .unwrap_or_default();
+    escrow += AUTO_RENEW_ESCROW
+        .may_load(deps.storage, denom.to_string())?
+        .unwrap_or_default();
+    if denom == REGISTER_FEE_DENOM {
+        escrow += AUCTION_ESCROW.may_load(deps.storage)?.unwrap_or_default();
+    }
+    Ok(balance_response.amount.amount.saturating_sub(escrow))
+}
+
+fn get_cw20_balance(deps: Deps<SeiQueryWrapper>, env: &Env, token: &Addr) -> StdResult<Uint128> {
+    let balance_response: Cw20BalanceResponse = deps.querier.query_wasm_smart(
+        token.to_string(),
+        &Cw20QueryMsg::Balance {
+            address: env.contract.address.to_string(),
+        },
+    )?;
+    Ok(balance_response.balance)
+}
 
-    let amount = balance_response.amount.amount;
+pub fn set_treasury(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    treasury: String,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    config.treasury = deps.api.addr_canonicalize(&treasury)?;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("method", "set_treasury")
+        .add_attribute("treasury", treasury))
+}
 
-    // let total_asset = Asset {
-    //     info: AssetInfo::NativeToken {
-    //         denom: balance_response.amount.denom,
-    //     },
-    //     amount,
-    // };
-    // let message = total_asset.into_msg(&deps.querier, info.sender);
+pub fn get_treasury(deps: Deps<SeiQueryWrapper>) -> StdResult<TreasuryResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let treasury = deps.api.addr_humanize(&config.treasury)?;
+    Ok(TreasuryResponse { treasury })
+}
 
-    let message = BankMsg::Send {
-        to_address: info.sender.to_string(),
-        amount: vec![balance_response.amount],
+pub fn set_fee_split(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    fee_split: Option<FeeSplit>,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    validate_fee_split(deps.as_ref(), &fee_split)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    config.fee_split = fee_split;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("method", "set_fee_split"))
+}
+
+pub fn validate_fee_split(
+    deps: Deps<SeiQueryWrapper>,
+    fee_split: &Option<FeeSplit>,
+) -> Result<(), ContractError> {
+    let fee_split = match fee_split {
+        Some(fee_split) => fee_split,
+        None => return Ok(()),
     };
+    let mut total_bps: u32 = 0;
+    for share in fee_split.shares.iter() {
+        if share.bps == 0 {
+            return Err(ContractError::InvalidFeeSplit {
+                description: String::from("Shares must be greater than zero"),
+            });
+        }
+        if let FeeRecipient::Address { address } = &share.recipient {
+            deps.api.addr_validate(address.as_str())?;
+        }
+        total_bps = total_bps.saturating_add(share.bps);
+    }
+    if total_bps != TOTAL_BPS {
+        return Err(ContractError::InvalidFeeSplit {
+            description: format!("Shares add up to {} bps instead of {}", total_bps, TOTAL_BPS),
+        });
+    }
+    Ok(())
+}
+
+pub fn get_fee_split(deps: Deps<SeiQueryWrapper>) -> StdResult<FeeSplitResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(FeeSplitResponse {
+        fee_split: config.fee_split,
+    })
+}
+
+// Amount owed to each share, the rounding remainder goes to the first one
+fn split_amount(fee_split: &FeeSplit, amount: Uint128) -> Vec<(&FeeRecipient, Uint128)> {
+    let mut amounts: Vec<(&FeeRecipient, Uint128)> = fee_split
+        .shares
+        .iter()
+        .map(|share| (&share.recipient, amount.multiply_ratio(share.bps, TOTAL_BPS)))
+        .collect();
+    let distributed: Uint128 = amounts.iter().map(|(_, share_amount)| *share_amount).sum();
+    if let Some((_, first_amount)) = amounts.first_mut() {
+        *first_amount += amount - distributed;
+    }
+    amounts
+}
+
+fn split_coin(fee_split: &FeeSplit, coin: &Coin) -> Vec<CosmosMsg> {
+    split_amount(fee_split, coin.amount)
+        .into_iter()
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|(recipient, amount)| {
+            let amount = vec![Coin {
+                denom: coin.denom.clone(),
+                amount,
+            }];
+            match recipient {
+                FeeRecipient::Address { address } => CosmosMsg::Bank(BankMsg::Send {
+                    to_address: address.to_string(),
+                    amount,
+                }),
+                FeeRecipient::Burn {} => CosmosMsg::Bank(BankMsg::Burn { amount }),
+            }
+        })
+        .collect()
+}
+
+fn split_cw20(fee_split: &FeeSplit, token: &Addr, amount: Uint128) -> StdResult<Vec<CosmosMsg>> {
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for (recipient, amount) in split_amount(fee_split, amount) {
+        if amount.is_zero() {
+            continue;
+        }
+        let msg = match recipient {
+            FeeRecipient::Address { address } => Cw20ExecuteMsg::Transfer {
+                recipient: address.to_string(),
+                amount,
+            },
+            FeeRecipient::Burn {} => Cw20ExecuteMsg::Burn { amount },
+        };
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&msg)?,
+            funds: vec![],
+        }));
+    }
+    Ok(messages)
+}
+
+// Fee split to apply to a payment as soon as it is received, if any
+fn get_register_fee_split(deps: Deps<SeiQueryWrapper>) -> StdResult<Option<FeeSplit>> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(config
+        .fee_split
+        .filter(|fee_split| fee_split.distribute_on_register))
+}
+
+fn split_register_revenue(
+    deps: Deps<SeiQueryWrapper>,
+    revenue: &Coin,
+) -> StdResult<Vec<CosmosMsg>> {
+    Ok(match get_register_fee_split(deps)? {
+        Some(fee_split) => split_coin(&fee_split, revenue),
+        None => vec![],
+    })
+}
+
+pub fn distribute(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    _info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let fee_split = config.fee_split.clone().ok_or(ContractError::BadRequest {
+        msg: String::from("No fee split is configured"),
+    })?;
+
+    let denoms = std::iter::once(String::from(REGISTER_FEE_DENOM)).chain(
+        config
+            .accepted_denoms
+            .iter()
+            .map(|accepted| accepted.denom.clone()),
+    );
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for denom in denoms {
+        let amount = get_withdrawable_balance(deps.as_ref(), &env, &denom)?;
+        messages.extend(split_coin(&fee_split, &Coin { denom, amount }));
+    }
+
+    let tokens = CW20_TOKENS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for token in tokens {
+        let amount = get_cw20_balance(deps.as_ref(), &env, &token)?;
+        messages.extend(split_cw20(&fee_split, &token, amount)?);
+    }
 
     Ok(Response::new()
-        .add_message(message)
-        .add_attribute("method", "withdraw")
-        .add_attribute("amount", amount))
+        .add_messages(messages)
+        .add_attribute("method", "distribute"))
 }
 
 pub fn set_config(
     deps: DepsMut<SeiQueryWrapper>,
     _env: Env,
     info: MessageInfo,
+    max_commitment_age: u64,
+    min_commitment_age: u64,
     min_registration_duration: u64,
-    tier1_price: u64,
-    tier2_price: u64,
-    tier3_price: u64,
     registrar_address: String,
     reverse_registrar_address: String,
     owner: String,
@@ -81,7 +402,13 @@ pub fn set_config(
     description: String,
 ) -> Result<Response, ContractError> {
     only_owner(deps.as_ref(), &info)?;
+    validate_commitment_age(min_commitment_age, max_commitment_age)?;
     let mut config = CONFIG.load(deps.storage)?;
+    validate_duration_limits(
+        min_registration_duration,
+        config.max_registration_duration,
+        config.max_expiry_duration,
+    )?;
 
     let registrar_address = deps.api.addr_canonicalize(registrar_address.as_str())?;
     let reverse_registrar_address = deps
@@ -89,10 +416,9 @@ pub fn set_config(
         .addr_canonicalize(reverse_registrar_address.as_str())?;
     let owner = deps.api.addr_canonicalize(owner.as_str())?;
 
+    config.max_commitment_age = max_commitment_age;
+    config.min_commitment_age = min_commitment_age;
     config.min_registration_duration = min_registration_duration;
-    config.tier1_price = tier1_price;
-    config.tier2_price = tier2_price;
-    config.tier3_price = tier3_price;
     config.registrar_address = registrar_address.clone();
     config.reverse_registrar_address = reverse_registrar_address.clone();
     config.owner = owner.clone();
@@ -102,13 +428,12 @@ pub fn set_config(
     CONFIG.save(deps.storage, &config)?;
     Ok(Response::new()
         .add_attribute("method", "set_config")
+        .add_attribute("max_commitment_age", max_commitment_age.to_string())
+        .add_attribute("min_commitment_age", min_commitment_age.to_string())
         .add_attribute(
             "min_registration_duration",
             min_registration_duration.to_string(),
         )
-        .add_attribute("tier1_price", tier1_price.to_string())
-        .add_attribute("tier2_price", tier2_price.to_string())
-        .add_attribute("tier3_price", tier3_price.to_string())
         .add_attribute("registrar_address", registrar_address.clone().to_string())
         .add_attribute(
             "enable_registration",
@@ -117,6 +442,95 @@ pub fn set_config(
         .add_attribute("owner", owner.clone().to_string()))
 }
 
+pub fn validate_commitment_age(
+    min_commitment_age: u64,
+    max_commitment_age: u64,
+) -> Result<(), ContractError> {
+    if min_commitment_age >= max_commitment_age {
+        return Err(ContractError::BadRequest {
+            msg: String::from("min_commitment_age must be less than max_commitment_age"),
+        });
+    }
+    Ok(())
+}
+
+pub fn validate_duration_limits(
+    min_registration_duration: u64,
+    max_registration_duration: Option<u64>,
+    max_expiry_duration: Option<u64>,
+) -> Result<(), ContractError> {
+    let max_registration_duration = match max_registration_duration {
+        Some(max_registration_duration) => max_registration_duration,
+        None => return Ok(()),
+    };
+    if max_registration_duration < min_registration_duration {
+        return Err(ContractError::BadRequest {
+            msg: String::from(
+                "max_registration_duration must not be less than min_registration_duration",
+            ),
+        });
+    }
+    if max_expiry_duration.is_some_and(|max_expiry| max_expiry < max_registration_duration) {
+        return Err(ContractError::BadRequest {
+            msg: String::from(
+                "max_expiry_duration must not be less than max_registration_duration",
+            ),
+        });
+    }
+    Ok(())
+}
+
+pub fn set_duration_limits(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    max_registration_duration: Option<u64>,
+    max_expiry_duration: Option<u64>,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    validate_duration_limits(
+        config.min_registration_duration,
+        max_registration_duration,
+        max_expiry_duration,
+    )?;
+    config.max_registration_duration = max_registration_duration;
+    config.max_expiry_duration = max_expiry_duration;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("method", "set_duration_limits")
+        .add_attribute(
+            "max_registration_duration",
+            format!("{:?}", max_registration_duration),
+        )
+        .add_attribute("max_expiry_duration", format!("{:?}", max_expiry_duration)))
+}
+
+fn validate_max_duration(config: &Config, duration: u64) -> Result<(), ContractError> {
+    match config.max_registration_duration {
+        Some(max_duration) if duration > max_duration => Err(ContractError::DurationTooLong {
+            input_duration: duration,
+            max_duration,
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn validate_expiry(config: &Config, env: &Env, expires: u64) -> Result<(), ContractError> {
+    let max_expiry_duration = match config.max_expiry_duration {
+        Some(max_expiry_duration) => max_expiry_duration,
+        None => return Ok(()),
+    };
+    let max_expires = env.block.time.seconds().saturating_add(max_expiry_duration);
+    if expires > max_expires {
+        return Err(ContractError::ExpiryTooFar {
+            expires,
+            max_expires,
+        });
+    }
+    Ok(())
+}
+
 pub fn set_enable_registration(
     deps: DepsMut<SeiQueryWrapper>,
     _env: Env,
@@ -132,6 +546,156 @@ pub fn set_enable_registration(
         .add_attribute("enable_registration", enable_registration.to_string()))
 }
 
+pub fn set_price_source(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    price_source: PriceSource,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    validate_price_source(deps.as_ref(), &price_source)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    config.price_source = price_source.clone();
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("method", "set_price_source")
+        .add_attribute("price_source", format!("{:?}", price_source)))
+}
+
+pub fn validate_price_source(
+    deps: Deps<SeiQueryWrapper>,
+    price_source: &PriceSource,
+) -> Result<(), ContractError> {
+    if let PriceSource::PriceFeed { contract, .. } = price_source {
+        deps.api.addr_validate(contract)?;
+    }
+    Ok(())
+}
+
+pub fn set_expiry_premium(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    expiry_premium: Option<ExpiryPremium>,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    validate_expiry_premium(&expiry_premium)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    config.expiry_premium = expiry_premium.clone();
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("method", "set_expiry_premium")
+        .add_attribute("expiry_premium", format!("{:?}", expiry_premium)))
+}
+
+pub fn validate_expiry_premium(
+    expiry_premium: &Option<ExpiryPremium>,
+) -> Result<(), ContractError> {
+    if let Some(expiry_premium) = expiry_premium {
+        if expiry_premium.duration == 0 {
+            return Err(ContractError::BadRequest {
+                msg: String::from("Expiry premium duration must be greater than zero"),
+            });
+        }
+        if let PremiumDecay::Exponential { half_life: 0 } = expiry_premium.decay {
+            return Err(ContractError::BadRequest {
+                msg: String::from("Expiry premium half_life must be greater than zero"),
+            });
+        }
+    }
+    Ok(())
+}
+
+pub fn get_expiry_premium(deps: Deps<SeiQueryWrapper>) -> StdResult<ExpiryPremiumResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ExpiryPremiumResponse {
+        expiry_premium: config.expiry_premium,
+    })
+}
+
+pub fn get_price_source(deps: Deps<SeiQueryWrapper>) -> StdResult<PriceSourceResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(PriceSourceResponse {
+        price_source: config.price_source,
+    })
+}
+
+pub fn set_accepted_denom(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    denom: String,
+    price_source: PriceSource,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    let accepted_denom = AcceptedDenom {
+        denom: denom.clone(),
+        price_source,
+    };
+    let mut config = CONFIG.load(deps.storage)?;
+    let mut accepted_denoms = config.accepted_denoms.clone();
+    accepted_denoms.retain(|accepted| accepted.denom != denom);
+    accepted_denoms.push(accepted_denom);
+    validate_accepted_denoms(deps.as_ref(), &accepted_denoms)?;
+    config.accepted_denoms = accepted_denoms;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("method", "set_accepted_denom")
+        .add_attribute("denom", denom))
+}
+
+pub fn remove_accepted_denom(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    config.accepted_denoms.retain(|accepted| accepted.denom != denom);
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("method", "remove_accepted_denom")
+        .add_attribute("denom", denom))
+}
+
+pub fn validate_accepted_denoms(
+    deps: Deps<SeiQueryWrapper>,
+    accepted_denoms: &[AcceptedDenom],
+) -> Result<(), ContractError> {
+    for (i, accepted) in accepted_denoms.iter().enumerate() {
+        if accepted.denom == REGISTER_FEE_DENOM
+            || accepted_denoms[..i]
+                .iter()
+                .any(|other| other.denom == accepted.denom)
+        {
+            return Err(ContractError::BadRequest {
+                msg: format!("{} is accepted more than once", accepted.denom),
+            });
+        }
+        validate_price_source(deps, &accepted.price_source)?;
+    }
+    Ok(())
+}
+
+pub fn get_accepted_denoms(deps: Deps<SeiQueryWrapper>) -> StdResult<AcceptedDenomsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(AcceptedDenomsResponse {
+        denoms: config.accepted_denoms,
+    })
+}
+
+fn get_denom_price_source<'a>(config: &'a Config, denom: &str) -> Option<&'a PriceSource> {
+    if denom == REGISTER_FEE_DENOM {
+        return Some(&config.price_source);
+    }
+    config
+        .accepted_denoms
+        .iter()
+        .find(|accepted| accepted.denom == denom)
+        .map(|accepted| &accepted.price_source)
+}
+
 pub fn set_whitelist_price(
     deps: DepsMut<SeiQueryWrapper>,
     _env: Env,
@@ -160,7 +724,7 @@ pub fn set_referal_percentage(
         });
     }
 
-    if whitelist_percentage > 50u32 || whitelist_percentage < 20u32 {
+    if !(20u32..=50u32).contains(&whitelist_percentage) {
         return Err(ContractError::ReferalPercentageError {
             description: Some(String::from("Whitelist percentage must be in 20~50")),
         });
@@ -176,72 +740,119 @@ pub fn set_referal_percentage(
         .add_attribute("whitelist_percentage", whitelist_percentage.to_string()))
 }
 
-// pub fn commit(
-//     deps: DepsMut,
-//     env: Env,
-//     _info: MessageInfo,
-//     commitment: String,
-// ) -> Result<Response, ContractError> {
-//     validate_enable_registration(deps.as_ref())?;
+pub fn commit(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    _info: MessageInfo,
+    commitment: String,
+) -> Result<Response, ContractError> {
+    validate_enable_registration(deps.as_ref())?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let last_commit_time = COMMITMENTS
+        .may_load(deps.storage, commitment.clone())?
+        .unwrap_or(0);
+    let current = env.block.time.seconds();
+
+    if last_commit_time + config.max_commitment_age > current {
+        return Err(ContractError::RecommitTooEarly {
+            commit_expired: last_commit_time + config.max_commitment_age,
+            current,
+        });
+    }
+
+    COMMITMENTS.save(deps.storage, commitment.clone(), &current)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "commit")
+        .add_attribute("commitment", commitment))
+}
 
-//     let config = CONFIG.load(deps.storage)?;
+pub fn prune_commitments(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    _info: MessageInfo,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let current = env.block.time.seconds();
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
 
-//     let last_commit_time = COMMITMENTS
-//         .may_load(deps.storage, commitment.clone())?
-//         .unwrap_or(0);
-//     let current = env.block.time.seconds();
+    let expired: Vec<String> = COMMITMENTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .filter_map(|item| match item {
+            Ok((commitment, commit_time)) => {
+                if commit_time + config.max_commitment_age < current {
+                    Some(Ok(commitment))
+                } else {
+                    None
+                }
+            }
+            Err(err) => Some(Err(err)),
+        })
+        .collect::<StdResult<Vec<String>>>()?;
 
-//     if last_commit_time + config.max_commitment_age > current {
-//         return Err(ContractError::RecommitTooEarly {
-//             commit_expired: last_commit_time + config.max_commitment_age,
-//             current,
-//         });
-//     }
+    for commitment in expired.iter() {
+        COMMITMENTS.remove(deps.storage, commitment.clone());
+    }
 
-//     COMMITMENTS.save(deps.storage, commitment.clone(), &current)?;
+    Ok(Response::new()
+        .add_attribute("method", "prune_commitments")
+        .add_attribute("pruned", expired.len().to_string()))
+}
 
-//     Ok(Response::new()
-//         .add_attribute("method", "commit")
-//         .add_attribute("commitment", commitment))
-// }
+// Name-indexed state is keyed by the canonical label, see `normalize_label`
+fn canonical_name(name: &str) -> Result<String, ContractError> {
+    normalize_label(name).map_err(|_| ContractError::InvalidName {})
+}
 
 fn validate_name(deps: Deps<SeiQueryWrapper>, name: String) -> Result<(), ContractError> {
-    if !get_is_valid_name(&name)?.is_valid_name {
+    if !get_is_valid_name(deps, &name)?.is_valid_name {
         return Err(ContractError::InvalidName {});
     }
+    check_blocklist(deps, &name)?;
 
-    if !is_available_name(deps, &name)? || !get_is_valid_name(&name)?.is_valid_name {
+    if !is_available_name(deps, &name)? {
         return Err(ContractError::UnavailabledName {});
     }
+
+    let name = canonical_name(&name)?;
+    if AUCTIONS.has(deps.storage, name.clone()) {
+        return Err(ContractError::NameInAuction { name });
+    }
     Ok(())
 }
 
-// pub fn consume_commitment(
-//     deps: DepsMut,
-//     env: Env,
-//     commitment: String,
-// ) -> Result<(), ContractError> {
-//     let config = CONFIG.load(deps.storage)?;
-//     let commit_time = COMMITMENTS.may_load(deps.storage, commitment.clone())?;
-//     if commit_time.is_none() {
-//         return Err(ContractError::ConsumeNonexistCommitment { commitment });
-//     }
-
-//     let commit_time = commit_time.unwrap();
-//     let current = env.block.time.seconds();
-//     if commit_time + config.min_commitment_age > current
-//         || commit_time + config.max_commitment_age < current
-//     {
-//         return Err(ContractError::CommitmentIsTooEarlyOrExpired {
-//             commit_expired: commit_time + config.max_commitment_age,
-//             commit_matured: commit_time + config.min_commitment_age,
-//             current,
-//         });
-//     }
-
-//     COMMITMENTS.remove(deps.storage, commitment);
-//     Ok(())
-// }
+fn consume_commitment(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: &Env,
+    commitment: String,
+) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let commit_time = COMMITMENTS.may_load(deps.storage, commitment.clone())?;
+    if commit_time.is_none() {
+        return Err(ContractError::ConsumeNonexistCommitment { commitment });
+    }
+
+    let commit_time = commit_time.unwrap();
+    let current = env.block.time.seconds();
+    if commit_time + config.min_commitment_age > current
+        || commit_time + config.max_commitment_age < current
+    {
+        return Err(ContractError::CommitmentIsTooEarlyOrExpired {
+            commit_expired: commit_time + config.max_commitment_age,
+            commit_matured: commit_time + config.min_commitment_age,
+            current,
+        });
+    }
+
+    COMMITMENTS.remove(deps.storage, commitment);
+    Ok(())
+}
 
 pub fn query_exchange_rates(deps: Deps<SeiQueryWrapper>) -> StdResult<ExchangeRatesResponse> {
     let querier = SeiQuerier::new(&deps.querier);
@@ -250,140 +861,892 @@ pub fn query_exchange_rates(deps: Deps<SeiQueryWrapper>) -> StdResult<ExchangeRa
     Ok(res)
 }
 
-pub fn get_cost(deps: Deps<SeiQueryWrapper>, name: String, duration: u64) -> Result<Uint128, ContractError> {
-    let exchange_rate = query_exchange_rates(deps)?;
+pub fn get_exchange_rate(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    denom: &str,
+    price_source: &PriceSource,
+) -> Result<Option<Decimal>, ContractError> {
+    let exchange_rate = match price_source {
+        PriceSource::Fixed {} => return Ok(None),
+        PriceSource::SeiOracle {
+            max_staleness_blocks,
+        } => {
+            let exchange_rates = query_exchange_rates(deps)?;
+            let pair = exchange_rates
+                .denom_oracle_exchange_rate_pairs
+                .iter()
+                .find(|pair| pair.denom == denom)
+                .ok_or(ContractError::PriceUnavailable {
+                    denom: denom.to_string(),
+                })?;
+
+            let last_update = pair.oracle_exchange_rate.last_update.u64();
+            let oldest_allowed = env.block.height.saturating_sub(*max_staleness_blocks);
+            if last_update < oldest_allowed {
+                return Err(ContractError::StalePrice {
+                    last_update,
+                    oldest_allowed,
+                });
+            }
+
+            let exchange_rate = pair.oracle_exchange_rate.exchange_rate;
+            let exchange_rate = exchange_rate.mul(Uint128::from(1000u128));
+            Decimal::from_atomics(exchange_rate, 3).map_err(|_| {
+                ContractError::PriceUnavailable {
+                    denom: denom.to_string(),
+                }
+            })?
+        }
+        PriceSource::PriceFeed {
+            contract,
+            max_staleness_secs,
+        } => {
+            let price_feed_response: PriceFeedResponse =
+                deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                    contract_addr: contract.clone(),
+                    msg: to_binary(&PriceFeedQueryMsg::Price {
+                        denom: denom.to_string(),
+                    })?,
+                }))?;
+
+            let oldest_allowed = env.block.time.seconds().saturating_sub(*max_staleness_secs);
+            if price_feed_response.last_updated < oldest_allowed {
+                return Err(ContractError::StalePrice {
+                    last_update: price_feed_response.last_updated,
+                    oldest_allowed,
+                });
+            }
+            price_feed_response.rate
+        }
+    };
+
+    if exchange_rate.is_zero() {
+        return Err(ContractError::PriceUnavailable {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(Some(exchange_rate))
+}
+
+pub fn get_cost(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    name: String,
+    duration: u64,
+) -> Result<Uint128, ContractError> {
+    let (cost, _) = get_cost_in_denom(deps, env, REGISTER_FEE_DENOM, name, duration, None)?;
+    Ok(cost)
+}
+
+// Returns the total cost in `denom` and the expiry premium included in it
+pub fn get_cost_in_denom(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    denom: &str,
+    name: String,
+    duration: u64,
+    discount: Option<&Discount>,
+) -> Result<(Uint128, Uint128), ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let min_duration = config.min_registration_duration;
-    let name_length = name.graphemes(true).count();
-    if name_length < 3 {
-        return Err(ContractError::NameTooShort {});
+    let price_source =
+        get_denom_price_source(&config, denom).ok_or(ContractError::UnexpectedDenom {
+            denom: denom.to_string(),
+        })?;
+    let (cost, premium) = get_price_units(deps, env, &config, name, duration)?;
+    let cost = match discount {
+        Some(discount) => apply_discount(cost, discount),
+        None => cost,
+    };
+    let premium = premium.min(cost);
+    let exchange_rate = get_exchange_rate(deps, env, denom, price_source)?;
+    Ok((
+        to_denom_amount(cost, exchange_rate),
+        to_denom_amount(premium, exchange_rate),
+    ))
+}
+
+// Converts price units to the payment denom, rounding up
+fn to_denom_amount(amount: Uint128, exchange_rate: Option<Decimal>) -> Uint128 {
+    match exchange_rate {
+        Some(exchange_rate) => amount.div_ceil(exchange_rate),
+        None => amount,
+    }
+}
+
+pub fn get_cw20_cost(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    token: &Addr,
+    name: String,
+    duration: u64,
+) -> Result<Uint128, ContractError> {
+    let rate = CW20_TOKENS
+        .may_load(deps.storage, token)?
+        .ok_or(ContractError::UnsupportedToken {
+            token: token.to_string(),
+        })?;
+    let config = CONFIG.load(deps.storage)?;
+    let (cost, _) = get_price_units(deps, env, &config, name, duration)?;
+    Ok(cost.div_ceil(rate))
+}
+
+fn is_valid_discount(discount: &Discount) -> bool {
+    match discount {
+        Discount::Percentage { percentage } => *percentage > 0 && *percentage <= 100,
+        Discount::Fixed { amount } => *amount > 0,
     }
+}
+
+fn apply_discount(cost: Uint128, discount: &Discount) -> Uint128 {
+    match discount {
+        Discount::Percentage { percentage } => cost - cost.multiply_ratio(*percentage, 100u32),
+        Discount::Fixed { amount } => cost.saturating_sub(Uint128::from(*amount)),
+    }
+}
+
+// Returns the cost and the expiry premium included in it, before conversion to a payment denom
+fn get_price_units(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    config: &Config,
+    name: String,
+    duration: u64,
+) -> Result<(Uint128, Uint128), ContractError> {
+    let breakdown = get_price_breakdown(deps, env, config, name, duration)?;
+    let cost = breakdown.base - breakdown.duration_discount + breakdown.premium;
+    Ok((cost, breakdown.premium))
+}
+
+// Components of the cost of a name, in price units
+struct PriceBreakdown {
+    annual_price: u64,
+    base: Uint128,
+    duration_discount: Uint128,
+    premium: Uint128,
+}
+
+fn get_price_breakdown(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    config: &Config,
+    name: String,
+    duration: u64,
+) -> Result<PriceBreakdown, ContractError> {
+    let name = canonical_name(&name)?;
+    let min_duration = config.min_registration_duration;
+    let name_length = name.graphemes(true).count() as u32;
+    let base_cost = match PREMIUM_NAMES.may_load(deps.storage, name.clone())? {
+        Some(PremiumName::Price { annual_price }) => annual_price,
+        Some(PremiumName::Reserved {}) => return Err(ContractError::ReservedName { name }),
+        None => get_price_tier(deps, name_length)?.annual_price,
+    };
     if duration < min_duration {
         return Err(ContractError::DurationTooShort {
             input_duration: duration,
-            min_duration: min_duration,
+            min_duration,
         });
     }
+    validate_max_duration(config, duration)?;
 
-    let base_cost = match name_length {
-        3 => config.tier1_price,
-        4 => config.tier2_price,
-        _ => config.tier3_price,
+    let base = Uint128::from(base_cost).multiply_ratio(duration, 31_536_000u64);
+    // The longest commitment the duration qualifies for applies
+    let duration_discount = match config
+        .duration_discounts
+        .iter()
+        .rev()
+        .find(|discount| duration >= discount.min_duration)
+    {
+        Some(discount) => base.multiply_ratio(discount.percentage, 100u32),
+        None => Uint128::zero(),
     };
-    let exchange_rate = exchange_rate.denom_oracle_exchange_rate_pairs.iter().find(|pair|pair.denom == String::from("usei")).unwrap();
-    let exchange_rate = exchange_rate.oracle_exchange_rate.exchange_rate;
-    let exchange_rate = exchange_rate.mul(Uint128::from(1000u128));
-    let exchange_rate = Decimal::from_atomics(exchange_rate, 3).unwrap();
-    Ok(Uint128::from(base_cost).multiply_ratio(duration, 31_536_000u64).div_ceil(exchange_rate))
+    let premium = get_released_premium(deps, env, config, &name)?;
+    Ok(PriceBreakdown {
+        annual_price: base_cost,
+        base,
+        duration_discount,
+        premium,
+    })
 }
 
-pub fn get_price(deps: Deps<SeiQueryWrapper>) -> StdResult<PriceResponse> {
+pub fn validate_duration_discounts(
+    mut duration_discounts: Vec<DurationDiscount>,
+) -> Result<Vec<DurationDiscount>, ContractError> {
+    duration_discounts.sort_by_key(|discount| discount.min_duration);
+    for (index, discount) in duration_discounts.iter().enumerate() {
+        if discount.percentage == 0 || discount.percentage >= 100 {
+            return Err(ContractError::InvalidDurationDiscount {
+                description: String::from("Percentage must be in 1~99"),
+            });
+        }
+        if index > 0 && duration_discounts[index - 1].min_duration == discount.min_duration {
+            return Err(ContractError::InvalidDurationDiscount {
+                description: format!(
+                    "Duplicated discount for min_duration {}",
+                    discount.min_duration
+                ),
+            });
+        }
+    }
+    Ok(duration_discounts)
+}
+
+pub fn set_duration_discounts(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    duration_discounts: Vec<DurationDiscount>,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    config.duration_discounts = validate_duration_discounts(duration_discounts)?;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("method", "set_duration_discounts")
+        .add_attribute("discounts", config.duration_discounts.len().to_string()))
+}
+
+pub fn get_duration_discounts(deps: Deps<SeiQueryWrapper>) -> StdResult<DurationDiscountsResponse> {
     let config = CONFIG.load(deps.storage)?;
-    Ok(PriceResponse {
-        tier1_price: config.tier1_price,
-        tier2_price: config.tier2_price,
-        tier3_price: config.tier3_price,
-        whitelist_price: config.whitelist_price,
+    Ok(DurationDiscountsResponse {
+        duration_discounts: config.duration_discounts,
     })
 }
 
-fn _register(
+pub fn validate_name_policy(name_policy: &NamePolicy) -> Result<(), ContractError> {
+    if name_policy.min_length == 0 {
+        return Err(ContractError::InvalidNamePolicy {
+            description: String::from("min_length must be at least 1"),
+        });
+    }
+    if name_policy
+        .max_length
+        .is_some_and(|max_length| max_length < name_policy.min_length)
+    {
+        return Err(ContractError::InvalidNamePolicy {
+            description: String::from("max_length must not be less than min_length"),
+        });
+    }
+    if name_policy.allowed_characters.is_empty() {
+        return Err(ContractError::InvalidNamePolicy {
+            description: String::from("At least one character class must be allowed"),
+        });
+    }
+    // Labels are matched in their canonical form, so the substrings must be too
+    for substring in name_policy.blocked_substrings.iter() {
+        if !normalize_label(substring).is_ok_and(|canonical| canonical == *substring) {
+            return Err(ContractError::InvalidNamePolicy {
+                description: format!("Blocked substring {} is not canonical", substring),
+            });
+        }
+    }
+    Ok(())
+}
+
+pub fn set_name_policy(
     deps: DepsMut<SeiQueryWrapper>,
-    env: Env,
-    name: String,
-    owner: String,
-    duration: u64,
-    resolver: Option<String>,
-    address: Option<String>,
-    reverse_record: bool,
-) -> Result<Vec<CosmosMsg>, ContractError> {
-    let mut messages: Vec<CosmosMsg> = vec![];
+    _env: Env,
+    info: MessageInfo,
+    name_policy: NamePolicy,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    validate_name_policy(&name_policy)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    config.name_policy = name_policy;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("method", "set_name_policy"))
+}
 
+pub fn get_name_policy(deps: Deps<SeiQueryWrapper>) -> StdResult<NamePolicyResponse> {
     let config = CONFIG.load(deps.storage)?;
+    Ok(NamePolicyResponse {
+        name_policy: config.name_policy,
+    })
+}
+
+fn get_released_premium(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    config: &Config,
+    name: &String,
+) -> Result<Uint128, ContractError> {
+    let expiry_premium = match &config.expiry_premium {
+        Some(expiry_premium) => expiry_premium,
+        None => return Ok(Uint128::zero()),
+    };
+    let label = get_label_from_name(name);
+    let id = get_token_id_from_label(&label);
     let registrar_address = deps
         .api
         .addr_humanize(&config.registrar_address)?
         .to_string();
+    let expires_response: GetExpiresResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: registrar_address.clone(),
+            msg: to_binary(&RegistrarQueryMsg::<WasmQuery>::GetExpires { id })?,
+        }))?;
+    // Never registered
+    if expires_response.expires == 0 {
+        return Ok(Uint128::zero());
+    }
+    let grace_period_response: GetGracePeriodResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: registrar_address,
+            msg: to_binary(&RegistrarQueryMsg::<WasmQuery>::GetGracePeriod {})?,
+        }))?;
 
-    let label: Vec<u8> = get_label_from_name(&name);
-    let token_id = get_token_id_from_label(&label);
+    let released = expires_response.expires + grace_period_response.grace_period;
+    let current = env.block.time.seconds();
+    if current <= released {
+        return Ok(Uint128::zero());
+    }
+    Ok(decay_premium(expiry_premium, current - released))
+}
 
-    // Register this contract to be temporary owner of the node at registrar
-    let register_registrar_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: registrar_address.clone(),
-        msg: to_binary(&RegistrarExecuteMsg::<Extension>::Register {
-            id: token_id.clone(),
-            owner: env.contract.address.to_string(),
-            name: name.clone(),
-            duration,
-            extension: Extension {
-                name: name.clone(),
-                description: config.description,
+fn decay_premium(expiry_premium: &ExpiryPremium, elapsed: u64) -> Uint128 {
+    if elapsed >= expiry_premium.duration {
+        return Uint128::zero();
+    }
+    let start_price = Uint128::from(expiry_premium.start_price);
+    match expiry_premium.decay {
+        PremiumDecay::Linear {} => {
+            start_price.multiply_ratio(expiry_premium.duration - elapsed, expiry_premium.duration)
+        }
+        PremiumDecay::Exponential { half_life } => {
+            let halvings = elapsed / half_life;
+            if halvings >= 128 {
+                return Uint128::zero();
+            }
+            // Interpolate linearly between two halvings
+            let price = start_price >> (halvings as u32);
+            price - price.multiply_ratio(elapsed % half_life, 2 * half_life)
+        }
+    }
+}
+
+pub fn get_price_tier(
+    deps: Deps<SeiQueryWrapper>,
+    name_length: u32,
+) -> Result<PriceTier, ContractError> {
+    let tiers = load_price_tiers(deps.storage)?;
+    if let Some(tier) = tiers.iter().find(|tier| tier_contains(tier, name_length)) {
+        return Ok(tier.clone());
+    }
+    match tiers.first() {
+        Some(tier) if name_length >= tier.min_length => Err(ContractError::NoPriceTier {
+            length: name_length,
+        }),
+        Some(tier) => Err(ContractError::NameTooShort {
+            length: name_length,
+            min_length: tier.min_length,
+        }),
+        None => Err(ContractError::NoPriceTier {
+            length: name_length,
+        }),
+    }
+}
+
+fn tier_contains(tier: &PriceTier, name_length: u32) -> bool {
+    name_length >= tier.min_length && tier.max_length.is_none_or(|max| name_length <= max)
+}
+
+fn load_price_tiers(storage: &dyn Storage) -> StdResult<Vec<PriceTier>> {
+    PRICE_TIERS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, tier)| tier))
+        .collect()
+}
+
+// Tiers must not be empty ranges and must not overlap each other.
+fn validate_price_tier(storage: &dyn Storage, tier: &PriceTier) -> Result<(), ContractError> {
+    if let Some(max_length) = tier.max_length {
+        if max_length < tier.min_length {
+            return Err(ContractError::InvalidPriceTier {
+                description: String::from("max_length must not be less than min_length"),
+            });
+        }
+    }
+    for other in load_price_tiers(storage)? {
+        if other.min_length == tier.min_length {
+            continue;
+        }
+        let overlaps =
+            tier_contains(&other, tier.min_length) || tier_contains(tier, other.min_length);
+        if overlaps {
+            return Err(ContractError::InvalidPriceTier {
+                description: format!(
+                    "Tier starting at {} overlaps the tier starting at {}",
+                    tier.min_length, other.min_length
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Moves a config with fixed tier prices to the current layout. Tier 1 and tier 2 prices become
+/// the tiers of 3 and 4 character names and tier 3 covers every longer name.
+pub fn migrate_config(
+    mut deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    max_commitment_age: u64,
+    min_commitment_age: u64,
+    price_source: PriceSource,
+) -> Result<Response, ContractError> {
+    if CONFIG.load(deps.storage).is_ok() {
+        return Ok(Response::new().add_attribute("method", "migrate"));
+    }
+    let legacy = LEGACY_CONFIG.load(deps.storage)?;
+    validate_commitment_age(min_commitment_age, max_commitment_age)?;
+    validate_price_source(deps.as_ref(), &price_source)?;
+    let registrar_address = deps.api.addr_humanize(&legacy.registrar_address)?;
+    let whitelisted = migrate_whitelist(deps.branch(), registrar_address.to_string())?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            max_commitment_age,
+            min_commitment_age,
+            min_registration_duration: legacy.min_registration_duration,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: NamePolicy::default(),
+            whitelist_price: legacy.whitelist_price,
+            price_source,
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: legacy.referal_percentage,
+            enable_registration: legacy.enable_registration,
+            registrar_address: legacy.registrar_address,
+            reverse_registrar_address: legacy.reverse_registrar_address,
+            owner: legacy.owner.clone(),
+            treasury: legacy.owner,
+            description: legacy.description,
+        },
+    )?;
+    save_price_tiers(
+        deps.storage,
+        vec![
+            PriceTier {
+                min_length: 3,
+                max_length: Some(3),
+                annual_price: legacy.tier1_price,
             },
-        })?,
-        funds: vec![],
-    });
-    messages.push(register_registrar_msg);
+            PriceTier {
+                min_length: 4,
+                max_length: Some(4),
+                annual_price: legacy.tier2_price,
+            },
+            PriceTier {
+                min_length: 5,
+                max_length: None,
+                annual_price: legacy.tier3_price,
+            },
+        ],
+    )?;
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("tier1_price", legacy.tier1_price.to_string())
+        .add_attribute("tier2_price", legacy.tier2_price.to_string())
+        .add_attribute("tier3_price", legacy.tier3_price.to_string())
+        .add_attribute("whitelisted", whitelisted.to_string()))
+}
 
+// Legacy whitelist entries only hold the node and percentage, the owner is read from the registry.
+// Entries of nodes without a record are dropped.
+fn migrate_whitelist(
+    deps: DepsMut<SeiQueryWrapper>,
+    registrar_address: String,
+) -> Result<usize, ContractError> {
+    let entries = LEGACY_WHITELIST
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, (Vec<u8>, u32))>>>()?;
+    if entries.is_empty() {
+        return Ok(0);
+    }
     let get_registry_response: GetRegistryResponse =
         deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-            contract_addr: registrar_address.clone(),
+            contract_addr: registrar_address,
             msg: to_binary(&RegistrarQueryMsg::<WasmQuery>::GetRegistry {})?,
         }))?;
     let registry_address = String::from(get_registry_response.registry);
 
-    // Set resolver of the node at registry
-    let nodehash = get_nodehash(deps.as_ref(), label)?;
-    let registry_set_resolver_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: registry_address.clone(),
-        msg: to_binary(&RegistryExecuteMsg::SetResolver {
-            node: nodehash.clone(),
-            resolver: resolver.clone(),
-        })?,
-        funds: vec![],
-    });
-    messages.push(registry_set_resolver_msg);
-
-    // Set address at resolver
-    if let Some(address) = address.clone() {
-        let set_address_resolver_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: resolver.clone().unwrap_or(registry_address),
-            msg: to_binary(&ResolverExecuteMsg::SetSeiAddress {
-                node: nodehash,
-                address: address,
-            })?,
-            funds: vec![],
-        });
-        messages.push(set_address_resolver_msg);
+    let mut migrated = 0;
+    for (ensname, (node, percentage)) in entries {
+        let record: StdResult<RecordResponse> =
+            deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: registry_address.clone(),
+                msg: to_binary(&RegistryQueryMsg::GetRecordByNode { node: node.clone() })?,
+            }));
+        let owner = match record {
+            Ok(record) => record.owner,
+            Err(_) => {
+                WHITELIST.remove(deps.storage, ensname);
+                continue;
+            }
+        };
+        WHITELIST.save(
+            deps.storage,
+            ensname,
+            &WhitelistEntry {
+                node,
+                owner,
+                percentage,
+                expires: None,
+            },
+        )?;
+        migrated += 1;
     }
+    Ok(migrated)
+}
 
-    // Transfer ownership of the node to user
-    let reclaim_registrar_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: registrar_address.clone(),
-        msg: to_binary(&RegistrarExecuteMsg::<Extension>::Reclaim {
-            id: token_id.clone(),
-            owner: owner.clone(),
-        })?,
-        funds: vec![],
-    });
-    messages.push(reclaim_registrar_msg);
-
-    // Transfer ownership of NFT to user
-    let transfer_nft_registrar_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: registrar_address.clone(),
-        msg: to_binary(&RegistrarExecuteMsg::<Extension>::TransferNft {
-            recipient: owner.clone(),
-            token_id: token_id,
-        })?,
-        funds: vec![],
-    });
-    messages.push(transfer_nft_registrar_msg);
+pub fn save_price_tiers(
+    storage: &mut dyn Storage,
+    tiers: Vec<PriceTier>,
+) -> Result<(), ContractError> {
+    for tier in tiers {
+        if PRICE_TIERS.has(storage, tier.min_length) {
+            return Err(ContractError::InvalidPriceTier {
+                description: format!("Tier starting at {} already exists", tier.min_length),
+            });
+        }
+        validate_price_tier(storage, &tier)?;
+        PRICE_TIERS.save(storage, tier.min_length, &tier)?;
+    }
+    Ok(())
+}
 
-    // if reverse_record {
-    //     if let Some(addr) = address {
-    //         set_reverse_record(deps, name, addr, resolver, owner.to_string())?;
-    //     }
-    // }
+pub fn add_price_tier(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    min_length: u32,
+    max_length: Option<u32>,
+    annual_price: u64,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    let tier = PriceTier {
+        min_length,
+        max_length,
+        annual_price,
+    };
+    save_price_tiers(deps.storage, vec![tier])?;
+    Ok(Response::new()
+        .add_attribute("method", "add_price_tier")
+        .add_attribute("min_length", min_length.to_string())
+        .add_attribute("max_length", format!("{:?}", max_length))
+        .add_attribute("annual_price", annual_price.to_string()))
+}
+
+pub fn update_price_tier(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    min_length: u32,
+    max_length: Option<u32>,
+    annual_price: u64,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    if !PRICE_TIERS.has(deps.storage, min_length) {
+        return Err(ContractError::NoPriceTier { length: min_length });
+    }
+    let tier = PriceTier {
+        min_length,
+        max_length,
+        annual_price,
+    };
+    validate_price_tier(deps.storage, &tier)?;
+    PRICE_TIERS.save(deps.storage, min_length, &tier)?;
+    Ok(Response::new()
+        .add_attribute("method", "update_price_tier")
+        .add_attribute("min_length", min_length.to_string())
+        .add_attribute("max_length", format!("{:?}", max_length))
+        .add_attribute("annual_price", annual_price.to_string()))
+}
+
+pub fn remove_price_tier(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    min_length: u32,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    if !PRICE_TIERS.has(deps.storage, min_length) {
+        return Err(ContractError::NoPriceTier { length: min_length });
+    }
+    PRICE_TIERS.remove(deps.storage, min_length);
+    Ok(Response::new()
+        .add_attribute("method", "remove_price_tier")
+        .add_attribute("min_length", min_length.to_string()))
+}
+
+pub fn set_premium_name(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    name: String,
+    premium: PremiumName,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    let name = canonical_name(&name)?;
+    PREMIUM_NAMES.save(deps.storage, name.clone(), &premium)?;
+    Ok(Response::new()
+        .add_attribute("method", "set_premium_name")
+        .add_attribute("name", name)
+        .add_attribute("premium", format!("{:?}", premium)))
+}
+
+pub fn remove_premium_name(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    let name = canonical_name(&name)?;
+    PREMIUM_NAMES.remove(deps.storage, name.clone());
+    Ok(Response::new()
+        .add_attribute("method", "remove_premium_name")
+        .add_attribute("name", name))
+}
+
+pub fn get_premium_names(
+    deps: Deps<SeiQueryWrapper>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<PremiumNamesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let names = PREMIUM_NAMES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(name, premium)| PremiumNameInfo { name, premium }))
+        .collect::<StdResult<Vec<PremiumNameInfo>>>()?;
+    Ok(PremiumNamesResponse { names })
+}
+
+fn blocklist_key(entry: BlocklistEntry) -> Result<(String, String), ContractError> {
+    let is_canonical = normalize_label(&entry.entry).is_ok_and(|label| label == entry.entry);
+    match entry.kind {
+        BlockKind::Label {} | BlockKind::Substring {} if !is_canonical => {
+            Err(ContractError::InvalidBlocklistEntry {
+                description: format!("{} is not canonical", entry.entry),
+            })
+        }
+        BlockKind::Label {} => Ok((
+            BLOCKED_LABEL_HASH.to_string(),
+            get_token_id_from_label(&get_label_from_name(&entry.entry)),
+        )),
+        BlockKind::LabelHash {} => {
+            let hash = entry.entry.to_lowercase();
+            if !hex::decode(&hash).is_ok_and(|hash| hash.len() == 32) {
+                return Err(ContractError::InvalidBlocklistEntry {
+                    description: format!("{} is not a hex encoded keccak256 hash", entry.entry),
+                });
+            }
+            Ok((BLOCKED_LABEL_HASH.to_string(), hash))
+        }
+        BlockKind::Substring {} => Ok((BLOCKED_SUBSTRING.to_string(), entry.entry)),
+    }
+}
+
+pub fn add_to_blocklist(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    entries: Vec<BlocklistEntry>,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    let count = entries.len();
+    for entry in entries {
+        BLOCKLIST.save(deps.storage, blocklist_key(entry)?, &true)?;
+    }
+    Ok(Response::new()
+        .add_attribute("method", "add_to_blocklist")
+        .add_attribute("entries", count.to_string()))
+}
+
+pub fn remove_from_blocklist(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    entries: Vec<BlocklistEntry>,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    let count = entries.len();
+    for entry in entries {
+        BLOCKLIST.remove(deps.storage, blocklist_key(entry)?);
+    }
+    Ok(Response::new()
+        .add_attribute("method", "remove_from_blocklist")
+        .add_attribute("entries", count.to_string()))
+}
+
+fn check_blocklist(deps: Deps<SeiQueryWrapper>, name: &String) -> Result<(), ContractError> {
+    let label_hash = get_token_id_from_label(&get_label_from_name(name));
+    if BLOCKLIST.has(deps.storage, (BLOCKED_LABEL_HASH.to_string(), label_hash)) {
+        return Err(ContractError::BlockedName { name: name.clone() });
+    }
+    for substring in BLOCKLIST.prefix(BLOCKED_SUBSTRING.to_string()).keys(
+        deps.storage,
+        None,
+        None,
+        Order::Ascending,
+    ) {
+        if name.contains(substring?.as_str()) {
+            return Err(ContractError::BlockedName { name: name.clone() });
+        }
+    }
+    Ok(())
+}
+
+pub fn get_blocklist(
+    deps: Deps<SeiQueryWrapper>,
+    start_after: Option<BlocklistEntry>,
+    limit: Option<u32>,
+) -> StdResult<BlocklistResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = match start_after {
+        Some(entry) => Some(Bound::exclusive(
+            blocklist_key(entry).map_err(|err| StdError::generic_err(err.to_string()))?,
+        )),
+        None => None,
+    };
+    let entries = BLOCKLIST
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(kind, entry)| BlocklistEntry {
+                entry,
+                kind: match kind.as_str() {
+                    BLOCKED_SUBSTRING => BlockKind::Substring {},
+                    _ => BlockKind::LabelHash {},
+                },
+            })
+        })
+        .collect::<StdResult<Vec<BlocklistEntry>>>()?;
+    Ok(BlocklistResponse { entries })
+}
+
+pub fn get_price_tiers(deps: Deps<SeiQueryWrapper>) -> StdResult<PriceTiersResponse> {
+    let tiers = load_price_tiers(deps.storage)?;
+    Ok(PriceTiersResponse { tiers })
+}
+
+pub fn get_price(deps: Deps<SeiQueryWrapper>) -> StdResult<PriceResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let price_tiers = load_price_tiers(deps.storage)?;
+    Ok(PriceResponse {
+        price_tiers,
+        whitelist_price: config.whitelist_price,
+    })
+}
+
+fn _register(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    name: String,
+    owner: String,
+    duration: u64,
+    resolver: Option<String>,
+    address: Option<String>,
+    reverse_record: bool,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut messages: Vec<CosmosMsg> = vec![];
+
+    let config = CONFIG.load(deps.storage)?;
+    validate_max_duration(&config, duration)?;
+    validate_expiry(
+        &config,
+        &env,
+        env.block.time.seconds().saturating_add(duration),
+    )?;
+    let registrar_address = deps
+        .api
+        .addr_humanize(&config.registrar_address)?
+        .to_string();
+
+    let label: Vec<u8> = get_label_from_name(&name);
+    let token_id = get_token_id_from_label(&label);
+
+    // Register this contract to be temporary owner of the node at registrar
+    let register_registrar_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: registrar_address.clone(),
+        msg: to_binary(&RegistrarExecuteMsg::<Extension>::Register {
+            id: token_id.clone(),
+            owner: env.contract.address.to_string(),
+            name: name.clone(),
+            duration,
+            extension: Extension {
+                name: name.clone(),
+                description: config.description,
+            },
+        })?,
+        funds: vec![],
+    });
+    messages.push(register_registrar_msg);
+
+    let get_registry_response: GetRegistryResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: registrar_address.clone(),
+            msg: to_binary(&RegistrarQueryMsg::<WasmQuery>::GetRegistry {})?,
+        }))?;
+    let registry_address = String::from(get_registry_response.registry);
+
+    // Set resolver of the node at registry
+    let nodehash = get_nodehash(deps.as_ref(), label)?;
+    let registry_set_resolver_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: registry_address.clone(),
+        msg: to_binary(&RegistryExecuteMsg::SetResolver {
+            node: nodehash.clone(),
+            resolver: resolver.clone(),
+        })?,
+        funds: vec![],
+    });
+    messages.push(registry_set_resolver_msg);
+
+    // Set address at resolver
+    if let Some(address) = address.clone() {
+        let set_address_resolver_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: resolver.clone().unwrap_or(registry_address),
+            msg: to_binary(&ResolverExecuteMsg::SetSeiAddress {
+                node: nodehash,
+                address,
+            })?,
+            funds: vec![],
+        });
+        messages.push(set_address_resolver_msg);
+    }
+
+    // Transfer ownership of the node to user
+    let reclaim_registrar_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: registrar_address.clone(),
+        msg: to_binary(&RegistrarExecuteMsg::<Extension>::Reclaim {
+            id: token_id.clone(),
+            owner: owner.clone(),
+        })?,
+        funds: vec![],
+    });
+    messages.push(reclaim_registrar_msg);
+
+    // Transfer ownership of NFT to user
+    let transfer_nft_registrar_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: registrar_address.clone(),
+        msg: to_binary(&RegistrarExecuteMsg::<Extension>::TransferNft {
+            recipient: owner.clone(),
+            token_id,
+        })?,
+        funds: vec![],
+    });
+    messages.push(transfer_nft_registrar_msg);
+
+    // if reverse_record {
+    //     if let Some(addr) = address {
+    //         set_reverse_record(deps, name, addr, resolver, owner.to_string())?;
+    //     }
+    // }
 
     if reverse_record {
         if let Some(addr) = address {
@@ -393,270 +1756,2040 @@ fn _register(
                     msg: to_binary(&RegistrarQueryMsg::<WasmQuery>::GetConfig {})?,
                 }))?;
 
-            let base_name = get_registrar_config_response.base_name;
+            let base_name = get_registrar_config_response.base_name;
+
+            let reverse_registrar_address = deps
+                .api
+                .addr_humanize(&config.reverse_registrar_address)?
+                .to_string();
+
+            let set_reverse_record_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: reverse_registrar_address,
+                msg: to_binary(&ReverseRegistrarExecuteMsg::SetNameForAddr {
+                    address: addr,
+                    owner,
+                    resolver,
+                    name: name + "." + base_name.as_str(),
+                    // name: name + &".sei".to_string(),
+                })?,
+                funds: vec![],
+            });
+            messages.push(set_reverse_record_msg);
+        }
+    }
+
+    Ok(messages)
+}
+
+// Returns the amount sent in excess of the cost, or an error if the funds don't cover it.
+fn validate_fund(info: &MessageInfo, cost: &Coin) -> Result<Coin, ContractError> {
+    if let Some(fund) = info.funds.iter().find(|fund| fund.denom != cost.denom) {
+        return Err(ContractError::UnexpectedDenom {
+            denom: fund.denom.clone(),
+        });
+    }
+    let amount: Uint128 = info.funds.iter().map(|fund| fund.amount).sum();
+    if amount < cost.amount {
+        return Err(ContractError::InsufficientFund {
+            amount,
+            required: cost.amount,
+        });
+    }
+
+    Ok(Coin {
+        denom: cost.denom.clone(),
+        amount: amount - cost.amount,
+    })
+}
+
+fn refund_excess(info: &MessageInfo, refund: &Coin) -> Option<CosmosMsg> {
+    if refund.amount.is_zero() {
+        return None;
+    }
+    Some(CosmosMsg::Bank(BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount: vec![refund.clone()],
+    }))
+}
+
+// Payments are made in a single accepted denom, `REGISTER_FEE_DENOM` if no funds are sent
+fn get_payment_denom(config: &Config, info: &MessageInfo) -> Result<String, ContractError> {
+    if let Some(fund) = info
+        .funds
+        .iter()
+        .find(|fund| get_denom_price_source(config, &fund.denom).is_none())
+    {
+        return Err(ContractError::UnexpectedDenom {
+            denom: fund.denom.clone(),
+        });
+    }
+    match info.funds.first() {
+        Some(fund) if info.funds.iter().any(|other| other.denom != fund.denom) => {
+            Err(ContractError::BadRequest {
+                msg: String::from("Payment must be made in a single denom"),
+            })
+        }
+        Some(fund) => Ok(fund.denom.clone()),
+        None => Ok(String::from(REGISTER_FEE_DENOM)),
+    }
+}
+
+fn validate_register_fund(
+    deps: Deps<SeiQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    duration: u64,
+    discount: Option<&Discount>,
+) -> Result<(Coin, Coin), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let denom = get_payment_denom(&config, &info)?;
+    let (amount, _) = get_cost_in_denom(deps, &env, &denom, name, duration, discount)?;
+    let cost = Coin { denom, amount };
+    let refund = validate_fund(&info, &cost)?;
+
+    Ok((cost, refund))
+}
+
+fn validate_enable_registration(deps: Deps<SeiQueryWrapper>) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.enable_registration {
+        return Err(ContractError::RegistrationDisabled {});
+    }
+    Ok(())
+}
+
+pub fn register(
+    mut deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    owner: String,
+    duration: u64,
+    secret: String,
+    resolver: Option<String>,
+    address: Option<String>,
+    reverse_record: bool,
+    promo_code: Option<String>,
+) -> Result<Response, ContractError> {
+    validate_name(deps.as_ref(), name.clone())?;
+    validate_enable_registration(deps.as_ref())?;
+
+    let discount = match &promo_code {
+        Some(code) => Some(redeem_promo_code(
+            deps.branch(),
+            &env,
+            code,
+            &name,
+            &info.sender,
+        )?),
+        None => None,
+    };
+    let (fund, refund) = validate_register_fund(
+        deps.as_ref(),
+        env.clone(),
+        info.clone(),
+        name.clone(),
+        duration,
+        discount.as_ref(),
+    )?;
+
+    let commitment = get_commitment(&name, &owner, &secret, &resolver, &address)?.commitment;
+    consume_commitment(deps.branch(), &env, commitment)?;
+
+    let mut messages = _register(
+        deps.branch(),
+        env.clone(),
+        name.clone(),
+        owner,
+        duration,
+        resolver,
+        address,
+        reverse_record,
+    )?;
+
+    if let Some(refund_msg) = refund_excess(&info, &refund) {
+        messages.push(refund_msg);
+    }
+    messages.extend(split_register_revenue(deps.as_ref(), &fund)?);
+
+    let label: Vec<u8> = get_label_from_name(&name);
+    let token_id = get_token_id_from_label(&label);
+    let nodehash = get_nodehash(deps.as_ref(), label.clone())?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("method", "register")
+        .add_attribute("name", name)
+        .add_attribute("label", format!("{:?}", label.clone()))
+        .add_attribute("token_id", token_id)
+        .add_attribute("nodehash", format!("{:?}", nodehash))
+        .add_attribute("refund", refund.amount))
+}
+
+pub fn referal_register(
+    mut deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    owner: String,
+    duration: u64,
+    secret: String,
+    resolver: Option<String>,
+    address: Option<String>,
+    referer_ensname: Option<String>,
+    reverse_record: bool,
+) -> Result<Response, ContractError> {
+    validate_name(deps.as_ref(), name.clone())?;
+    validate_enable_registration(deps.as_ref())?;
+
+    let (fund, refund) = validate_register_fund(
+        deps.as_ref(),
+        env.clone(),
+        info.clone(),
+        name.clone(),
+        duration,
+        None,
+    )?;
+
+    let commitment = get_commitment(&name, &owner, &secret, &resolver, &address)?.commitment;
+    consume_commitment(deps.branch(), &env, commitment)?;
+
+    let mut messages = _register(
+        deps.branch(),
+        env.clone(),
+        name.clone(),
+        owner.clone(),
+        duration,
+        resolver,
+        address,
+        reverse_record,
+    )?;
+
+    if let Some(refund_msg) = refund_excess(&info, &refund) {
+        messages.push(refund_msg);
+    }
+
+    let label: Vec<u8> = get_label_from_name(&name);
+    let token_id = get_token_id_from_label(&label);
+    let nodehash = get_nodehash(deps.as_ref(), label.clone());
+
+    let mut revenue = fund.clone();
+    let mut referal_attributes: Vec<Attribute> = vec![];
+    if let Some(referer) = referer_ensname {
+        match send_referal_funds(deps.branch(), env, info, &fund, referer) {
+            Ok((referal_owner, referal_fund_amount)) => {
+                revenue.amount = revenue.amount.saturating_sub(referal_fund_amount);
+                referal_attributes.push(Attribute::new("referal_owner", referal_owner));
+                referal_attributes.push(Attribute::new("referal_fund", referal_fund_amount));
+            }
+            // The registration goes through without a referral
+            Err(err @ ContractError::ReferrerNotFound { .. }) => {
+                referal_attributes.push(Attribute::new("referal_error", err.to_string()));
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    messages.extend(split_register_revenue(deps.as_ref(), &revenue)?);
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("method", "register")
+        .add_attribute("name", name)
+        .add_attribute("label", format!("{:?}", label.clone()))
+        .add_attribute("token_id", token_id)
+        .add_attribute("nodehash", format!("{:?}", nodehash))
+        .add_attribute("refund", refund.amount)
+        .add_attributes(referal_attributes))
+}
+
+// Credits the referral reward to the owner of the referrer name, it is paid out on claim
+pub fn send_referal_funds(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    _info: MessageInfo,
+    fund: &Coin,
+    referer_ensname: String,
+) -> Result<(Addr, Uint128), ContractError> {
+    let referer_ensname =
+        normalize_label(&referer_ensname).map_err(|_| ContractError::ReferrerNotFound {
+            name: referer_ensname.clone(),
+        })?;
+    let referal_owner = get_referer_owner(deps.as_ref(), &referer_ensname)?;
+    let mut stats = REFERRAL_STATS
+        .may_load(deps.storage, referer_ensname.clone())?
+        .unwrap_or_default();
+
+    let (percentage, _) = get_referral_percentage(
+        deps.as_ref(),
+        &env,
+        referer_ensname.clone(),
+        Some(&referal_owner),
+        stats.referrals,
+    )?;
+    let mut referal_fund = fund.clone();
+    referal_fund.amount = fund.amount.multiply_ratio(percentage as u128, 100u128);
+    let amount = referal_fund.amount;
+
+    stats.referrals += 1;
+    add_coin(&mut stats.volume, fund);
+    add_coin(&mut stats.rewards, &referal_fund);
+    add_coin(&mut stats.unclaimed, &referal_fund);
+    REFERRAL_STATS.save(deps.storage, referer_ensname.clone(), &stats)?;
+
+    let key = (&referal_owner, referer_ensname);
+    let mut unclaimed = REFERRAL_REWARDS
+        .may_load(deps.storage, key.clone())?
+        .unwrap_or_default();
+    add_coin(&mut unclaimed, &referal_fund);
+    REFERRAL_REWARDS.save(deps.storage, key, &unclaimed)?;
+    let escrow = REFERRAL_ESCROW
+        .may_load(deps.storage, referal_fund.denom.clone())?
+        .unwrap_or_default();
+    REFERRAL_ESCROW.save(deps.storage, referal_fund.denom, &(escrow + amount))?;
+
+    Ok((referal_owner, amount))
+}
+
+// Whitelisted names get the greater of their own percentage and the tier reached,
+// other names the tier reached or `referal_percentage.0` if there are no tiers.
+fn get_referral_percentage(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    referer_ensname: String,
+    referal_owner: Option<&Addr>,
+    referrals: u64,
+) -> StdResult<(u32, Option<ReferralTier>)> {
+    let config = CONFIG.load(deps.storage)?;
+    let tier = REFERRAL_TIERS
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::inclusive(referrals)),
+            Order::Descending,
+        )
+        .next()
+        .transpose()?
+        .map(|(_, tier)| tier);
+    let mut percentage = match &tier {
+        Some(tier) => tier.percentage,
+        None if REFERRAL_TIERS.is_empty(deps.storage) => config.referal_percentage.0,
+        None => 0,
+    };
+
+    if let Some(referal_owner) = referal_owner {
+        if let Some(whitelist_percentage) =
+            get_whitelist_percentage(deps, env, referer_ensname, referal_owner)?
+        {
+            percentage = percentage.max(whitelist_percentage);
+        }
+    }
+    Ok((percentage, tier))
+}
+
+pub fn set_promo_code(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    code_hash: String,
+    promo_code: PromoCode,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    if !hex::decode(&code_hash).is_ok_and(|hash| hash.len() == 32) {
+        return Err(ContractError::InvalidPromoCode {
+            description: String::from("code_hash must be a hex encoded keccak256 hash"),
+        });
+    }
+    if !is_valid_discount(&promo_code.discount) {
+        return Err(ContractError::InvalidPromoCode {
+            description: String::from(
+                "Discount must be a percentage in 1~100 or a positive amount",
+            ),
+        });
+    }
+    if let (Some(start_time), Some(end_time)) = (promo_code.start_time, promo_code.end_time) {
+        if end_time <= start_time {
+            return Err(ContractError::InvalidPromoCode {
+                description: String::from("end_time must be after start_time"),
+            });
+        }
+    }
+    if let (Some(min_length), Some(max_length)) = (promo_code.min_length, promo_code.max_length) {
+        if max_length < min_length {
+            return Err(ContractError::InvalidPromoCode {
+                description: String::from("max_length must not be less than min_length"),
+            });
+        }
+    }
+
+    let code_hash = code_hash.to_lowercase();
+    PROMO_CODES.save(deps.storage, code_hash.clone(), &promo_code)?;
+    Ok(Response::new()
+        .add_attribute("method", "set_promo_code")
+        .add_attribute("code_hash", code_hash))
+}
+
+pub fn remove_promo_code(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    code_hash: String,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    let code_hash = code_hash.to_lowercase();
+    PROMO_CODES.remove(deps.storage, code_hash.clone());
+    Ok(Response::new()
+        .add_attribute("method", "remove_promo_code")
+        .add_attribute("code_hash", code_hash))
+}
+
+// Checks everything but the per-address limit, returns the code hash and the promo code
+fn check_promo_code(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    code: &str,
+    name: &str,
+) -> Result<(String, PromoCode), ContractError> {
+    let code_hash = hex::encode(keccak256(code.as_bytes()));
+    let promo_code = PROMO_CODES
+        .may_load(deps.storage, code_hash.clone())?
+        .ok_or(ContractError::InvalidPromoCode {
+            description: String::from("Unknown promo code"),
+        })?;
+
+    let now = env.block.time.seconds();
+    if promo_code
+        .start_time
+        .is_some_and(|start_time| now < start_time)
+        || promo_code.end_time.is_some_and(|end_time| now >= end_time)
+    {
+        return Err(ContractError::InvalidPromoCode {
+            description: String::from("The promo code is not active"),
+        });
+    }
+    let uses = PROMO_CODE_USES
+        .may_load(deps.storage, code_hash.clone())?
+        .unwrap_or_default();
+    if promo_code.max_uses.is_some_and(|max_uses| uses >= max_uses) {
+        return Err(ContractError::InvalidPromoCode {
+            description: String::from("The promo code has been used up"),
+        });
+    }
+    let name_length = name.graphemes(true).count() as u32;
+    if promo_code
+        .min_length
+        .is_some_and(|min_length| name_length < min_length)
+        || promo_code
+            .max_length
+            .is_some_and(|max_length| name_length > max_length)
+    {
+        return Err(ContractError::InvalidPromoCode {
+            description: format!(
+                "The promo code does not apply to names of length {}",
+                name_length
+            ),
+        });
+    }
+    Ok((code_hash, promo_code))
+}
+
+fn redeem_promo_code(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: &Env,
+    code: &str,
+    name: &str,
+    sender: &Addr,
+) -> Result<Discount, ContractError> {
+    let (code_hash, promo_code) = check_promo_code(deps.as_ref(), env, code, name)?;
+    let address_uses = PROMO_CODE_ADDRESS_USES
+        .may_load(deps.storage, (code_hash.clone(), sender))?
+        .unwrap_or_default();
+    if promo_code
+        .max_uses_per_address
+        .is_some_and(|max_uses| address_uses >= max_uses)
+    {
+        return Err(ContractError::InvalidPromoCode {
+            description: format!("{} has used the promo code too many times", sender),
+        });
+    }
+
+    let uses = PROMO_CODE_USES
+        .may_load(deps.storage, code_hash.clone())?
+        .unwrap_or_default();
+    PROMO_CODE_USES.save(deps.storage, code_hash.clone(), &(uses + 1))?;
+    PROMO_CODE_ADDRESS_USES.save(deps.storage, (code_hash, sender), &(address_uses + 1))?;
+    Ok(promo_code.discount)
+}
+
+pub fn get_promo_code(
+    deps: Deps<SeiQueryWrapper>,
+    code_hash: String,
+) -> StdResult<PromoCodeResponse> {
+    let code_hash = code_hash.to_lowercase();
+    let promo_code = PROMO_CODES.load(deps.storage, code_hash.clone())?;
+    let uses = PROMO_CODE_USES
+        .may_load(deps.storage, code_hash.clone())?
+        .unwrap_or_default();
+    Ok(PromoCodeResponse {
+        code_hash,
+        promo_code,
+        uses,
+    })
+}
+
+pub fn set_merkle_campaign(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    campaign: String,
+    merkle_campaign: MerkleCampaign,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    if !hex::decode(&merkle_campaign.merkle_root).is_ok_and(|root| root.len() == 32) {
+        return Err(ContractError::BadRequest {
+            msg: String::from("merkle_root must be a hex encoded 32 byte hash"),
+        });
+    }
+    if merkle_campaign
+        .discount
+        .as_ref()
+        .is_some_and(|discount| !is_valid_discount(discount))
+    {
+        return Err(ContractError::BadRequest {
+            msg: String::from("Discount must be a percentage in 1~100 or a positive amount"),
+        });
+    }
+    let config = CONFIG.load(deps.storage)?;
+    if merkle_campaign.duration < config.min_registration_duration {
+        return Err(ContractError::DurationTooShort {
+            input_duration: merkle_campaign.duration,
+            min_duration: config.min_registration_duration,
+        });
+    }
+    validate_max_duration(&config, merkle_campaign.duration)?;
+
+    MERKLE_CAMPAIGNS.save(deps.storage, campaign.clone(), &merkle_campaign)?;
+    Ok(Response::new()
+        .add_attribute("method", "set_merkle_campaign")
+        .add_attribute("campaign", campaign)
+        .add_attribute("merkle_root", merkle_campaign.merkle_root))
+}
+
+pub fn remove_merkle_campaign(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    campaign: String,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    if !MERKLE_CAMPAIGNS.has(deps.storage, campaign.clone()) {
+        return Err(ContractError::CampaignNotFound { campaign });
+    }
+    MERKLE_CAMPAIGNS.remove(deps.storage, campaign.clone());
+    Ok(Response::new()
+        .add_attribute("method", "remove_merkle_campaign")
+        .add_attribute("campaign", campaign))
+}
+
+// Hashes the leaf up the tree with each sibling, pairs are hashed in sorted order
+fn verify_merkle_proof(merkle_root: &str, leaf: Vec<u8>, proof: &[String]) -> bool {
+    let mut hash = leaf;
+    for sibling in proof {
+        let sibling = match hex::decode(sibling) {
+            Ok(sibling) if sibling.len() == 32 => sibling,
+            _ => return false,
+        };
+        hash = if hash <= sibling {
+            keccak256(&[hash, sibling].concat())
+        } else {
+            keccak256(&[sibling, hash].concat())
+        };
+    }
+    hex::encode(hash) == merkle_root.to_lowercase()
+}
+
+pub fn claim_with_proof(
+    mut deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    campaign: String,
+    name: String,
+    owner: String,
+    secret: String,
+    resolver: Option<String>,
+    address: Option<String>,
+    reverse_record: bool,
+    proof: Vec<String>,
+) -> Result<Response, ContractError> {
+    validate_name(deps.as_ref(), name.clone())?;
+    validate_enable_registration(deps.as_ref())?;
+    let canonical = canonical_name(&name)?;
+    let premium_name = PREMIUM_NAMES.may_load(deps.storage, canonical.clone())?;
+    if let Some(PremiumName::Reserved {}) = premium_name {
+        return Err(ContractError::ReservedName { name: canonical });
+    }
+
+    let merkle_campaign = MERKLE_CAMPAIGNS
+        .may_load(deps.storage, campaign.clone())?
+        .ok_or(ContractError::CampaignNotFound {
+            campaign: campaign.clone(),
+        })?;
+    if merkle_campaign
+        .end_time
+        .is_some_and(|end_time| env.block.time.seconds() >= end_time)
+    {
+        return Err(ContractError::BadRequest {
+            msg: format!("Campaign {} has ended", campaign),
+        });
+    }
+    if MERKLE_CLAIMS.has(deps.storage, (campaign.clone(), &info.sender)) {
+        return Err(ContractError::AlreadyClaimed {
+            campaign,
+            address: info.sender.to_string(),
+        });
+    }
+    let leaf = keccak256(info.sender.as_bytes());
+    if !verify_merkle_proof(&merkle_campaign.merkle_root, leaf, &proof) {
+        return Err(ContractError::InvalidMerkleProof {
+            campaign,
+            address: info.sender.to_string(),
+        });
+    }
+    MERKLE_CLAIMS.save(deps.storage, (campaign.clone(), &info.sender), &true)?;
+
+    let payment = match &merkle_campaign.discount {
+        Some(discount) => Some(validate_register_fund(
+            deps.as_ref(),
+            env.clone(),
+            info.clone(),
+            name.clone(),
+            merkle_campaign.duration,
+            Some(discount),
+        )?),
+        None => {
+            if !info.funds.is_empty() {
+                return Err(ContractError::BadRequest {
+                    msg: String::from("The claim is free, no funds expected"),
+                });
+            }
+            // Free claims only cover tier-priced names, premium and just released
+            // names need a campaign with a discount
+            let config = CONFIG.load(deps.storage)?;
+            let released_premium = get_released_premium(deps.as_ref(), &env, &config, &canonical)?;
+            if premium_name.is_some() || !released_premium.is_zero() {
+                return Err(ContractError::PremiumNotClaimable { name: canonical });
+            }
+            None
+        }
+    };
+
+    let commitment = get_commitment(&name, &owner, &secret, &resolver, &address)?.commitment;
+    consume_commitment(deps.branch(), &env, commitment)?;
+
+    let mut messages = _register(
+        deps.branch(),
+        env,
+        name.clone(),
+        owner,
+        merkle_campaign.duration,
+        resolver,
+        address,
+        reverse_record,
+    )?;
+
+    let mut refund_amount = Uint128::zero();
+    if let Some((fund, refund)) = payment {
+        if let Some(refund_msg) = refund_excess(&info, &refund) {
+            messages.push(refund_msg);
+        }
+        messages.extend(split_register_revenue(deps.as_ref(), &fund)?);
+        refund_amount = refund.amount;
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("method", "claim_with_proof")
+        .add_attribute("campaign", campaign)
+        .add_attribute("name", name)
+        .add_attribute("refund", refund_amount))
+}
+
+pub fn get_merkle_campaign(
+    deps: Deps<SeiQueryWrapper>,
+    campaign: String,
+) -> StdResult<MerkleCampaignResponse> {
+    let merkle_campaign = MERKLE_CAMPAIGNS.load(deps.storage, campaign.clone())?;
+    Ok(MerkleCampaignResponse {
+        campaign,
+        merkle_campaign,
+    })
+}
+
+pub fn get_is_claimed(
+    deps: Deps<SeiQueryWrapper>,
+    campaign: String,
+    address: String,
+) -> StdResult<IsClaimedResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let claimed = MERKLE_CLAIMS.has(deps.storage, (campaign, &address));
+    Ok(IsClaimedResponse { claimed })
+}
+
+pub fn set_referral_tiers(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    tiers: Vec<ReferralTier>,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    for (i, tier) in tiers.iter().enumerate() {
+        if tier.percentage > 50u32 {
+            return Err(ContractError::ReferalPercentageError {
+                description: Some(String::from("Tier percentage must be in 0~50")),
+            });
+        }
+        if tiers[..i]
+            .iter()
+            .any(|other| other.min_referrals == tier.min_referrals)
+        {
+            return Err(ContractError::ReferalPercentageError {
+                description: Some(format!(
+                    "Tier starting at {} referrals is set more than once",
+                    tier.min_referrals
+                )),
+            });
+        }
+    }
+
+    REFERRAL_TIERS.clear(deps.storage);
+    for tier in tiers.iter() {
+        REFERRAL_TIERS.save(deps.storage, tier.min_referrals, tier)?;
+    }
+    Ok(Response::new()
+        .add_attribute("method", "set_referral_tiers")
+        .add_attribute("tiers", tiers.len().to_string()))
+}
+
+pub fn get_referral_tiers(deps: Deps<SeiQueryWrapper>) -> StdResult<ReferralTiersResponse> {
+    let tiers = REFERRAL_TIERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, tier)| tier))
+        .collect::<StdResult<Vec<ReferralTier>>>()?;
+    Ok(ReferralTiersResponse { tiers })
+}
+
+pub fn get_referral_tier(
+    deps: Deps<SeiQueryWrapper>,
+    env: Env,
+    name: String,
+) -> StdResult<ReferralTierResponse> {
+    let name = normalize_label(&name).map_err(|err| StdError::generic_err(err.to_string()))?;
+    let referrals = REFERRAL_STATS
+        .may_load(deps.storage, name.clone())?
+        .unwrap_or_default()
+        .referrals;
+    let referal_owner = get_referer_owner(deps, &name).ok();
+    let (percentage, tier) =
+        get_referral_percentage(deps, &env, name.clone(), referal_owner.as_ref(), referrals)?;
+    Ok(ReferralTierResponse {
+        name,
+        referrals,
+        tier,
+        percentage,
+    })
+}
+
+fn get_referer_owner(
+    deps: Deps<SeiQueryWrapper>,
+    referer_ensname: &String,
+) -> Result<Addr, ContractError> {
+    get_name_owner(deps, referer_ensname).map_err(|_| ContractError::ReferrerNotFound {
+        name: referer_ensname.clone(),
+    })
+}
+
+// Returns the owner of the node of `name` at the registry
+fn get_name_owner(deps: Deps<SeiQueryWrapper>, name: &String) -> StdResult<Addr> {
+    let config = CONFIG.load(deps.storage)?;
+    let registrar_address = deps
+        .api
+        .addr_humanize(&config.registrar_address)?
+        .to_string();
+
+    let label = get_label_from_name(name);
+    let nodehash = get_nodehash(deps, label)?;
+    let get_registry_response: GetRegistryResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: registrar_address.clone(),
+            msg: to_binary(&RegistrarQueryMsg::<WasmQuery>::GetRegistry {})?,
+        }))?;
+    let registry_address = String::from(get_registry_response.registry);
+
+    let record: RecordResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: registry_address,
+        msg: to_binary(&RegistryQueryMsg::GetRecordByNode { node: nodehash })?,
+    }))?;
+    Ok(record.owner)
+}
+
+fn add_coin(coins: &mut Vec<Coin>, coin: &Coin) {
+    if coin.amount.is_zero() {
+        return;
+    }
+    match coins.iter_mut().find(|existing| existing.denom == coin.denom) {
+        Some(existing) => existing.amount += coin.amount,
+        None => coins.push(coin.clone()),
+    }
+}
+
+fn sub_coin(coins: &mut Vec<Coin>, coin: &Coin) {
+    if let Some(existing) = coins
+        .iter_mut()
+        .find(|existing| existing.denom == coin.denom)
+    {
+        existing.amount = existing.amount.saturating_sub(coin.amount);
+    }
+    coins.retain(|existing| !existing.amount.is_zero());
+}
+
+// Unclaimed rewards of `address` per referrer name
+fn load_referral_rewards(
+    storage: &dyn Storage,
+    address: &Addr,
+) -> StdResult<Vec<(String, Vec<Coin>)>> {
+    REFERRAL_REWARDS
+        .prefix(address)
+        .range(storage, None, None, Order::Ascending)
+        .collect()
+}
+
+pub fn claim_referral_rewards(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut unclaimed: Vec<Coin> = vec![];
+    for (name, rewards) in load_referral_rewards(deps.storage, &info.sender)? {
+        REFERRAL_REWARDS.remove(deps.storage, (&info.sender, name.clone()));
+        let mut stats = REFERRAL_STATS.load(deps.storage, name.clone())?;
+        for coin in rewards.iter() {
+            sub_coin(&mut stats.unclaimed, coin);
+            add_coin(&mut unclaimed, coin);
+        }
+        REFERRAL_STATS.save(deps.storage, name, &stats)?;
+    }
+    if unclaimed.is_empty() {
+        return Err(ContractError::BadRequest {
+            msg: String::from("No referral rewards to claim"),
+        });
+    }
+    for coin in unclaimed.iter() {
+        let escrow = REFERRAL_ESCROW.load(deps.storage, coin.denom.clone())?;
+        REFERRAL_ESCROW.save(
+            deps.storage,
+            coin.denom.clone(),
+            &escrow.saturating_sub(coin.amount),
+        )?;
+    }
+
+    let amount = unclaimed
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: unclaimed,
+        })
+        .add_attribute("method", "claim_referral_rewards")
+        .add_attribute("referrer", info.sender)
+        .add_attribute("amount", amount))
+}
+
+pub fn get_referral_stats(
+    deps: Deps<SeiQueryWrapper>,
+    name: String,
+) -> StdResult<ReferralStatsResponse> {
+    let name = normalize_label(&name).map_err(|err| StdError::generic_err(err.to_string()))?;
+    let stats = REFERRAL_STATS
+        .may_load(deps.storage, name.clone())?
+        .unwrap_or_default();
+    Ok(ReferralStatsResponse { name, stats })
+}
+
+pub fn get_referral_rewards(
+    deps: Deps<SeiQueryWrapper>,
+    address: String,
+) -> StdResult<ReferralRewardsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let mut unclaimed: Vec<Coin> = vec![];
+    for (_, rewards) in load_referral_rewards(deps.storage, &address)? {
+        for coin in rewards.iter() {
+            add_coin(&mut unclaimed, coin);
+        }
+    }
+    Ok(ReferralRewardsResponse { address, unclaimed })
+}
+
+// Percentage of a whitelist entry that has not expired and whose name has not changed owner
+fn get_whitelist_percentage(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    ensname: String,
+    owner: &Addr,
+) -> StdResult<Option<u32>> {
+    let entry = WHITELIST.may_load(deps.storage, ensname)?;
+    Ok(entry
+        .filter(|entry| entry.owner == *owner)
+        .filter(|entry| {
+            entry
+                .expires
+                .is_none_or(|expires| env.block.time.seconds() < expires)
+        })
+        .map(|entry| entry.percentage))
+}
+
+pub fn owner_register(
+    mut deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    owner: String,
+    duration: u64,
+    resolver: Option<String>,
+    address: Option<String>,
+    reverse_record: bool,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    let name = canonical_name(&name)?;
+
+    if !is_available_name(deps.as_ref(), &name)? {
+        return Err(ContractError::UnavailabledName {});
+    }
+    if AUCTIONS.has(deps.storage, name.clone()) {
+        return Err(ContractError::NameInAuction { name });
+    }
+
+    let messages = _register(
+        deps.branch(),
+        env.clone(),
+        name.clone(),
+        owner,
+        duration,
+        resolver,
+        address,
+        reverse_record,
+    )?;
+
+    let label: Vec<u8> = get_label_from_name(&name);
+    let token_id = get_token_id_from_label(&label);
+    let nodehash = get_nodehash(deps.as_ref(), label.clone())?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("method", "owner_register")
+        .add_attribute("name", name)
+        .add_attribute("label", format!("{:?}", label.clone()))
+        .add_attribute("token_id", token_id)
+        .add_attribute("nodehash", format!("{:?}", nodehash)))
+}
+
+fn _renew(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    _info: MessageInfo,
+    token_id: String,
+    duration: u64,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let config = CONFIG.load(deps.storage)?;
+    validate_max_duration(&config, duration)?;
+    let registrar_address = deps
+        .api
+        .addr_humanize(&config.registrar_address)?
+        .to_string();
+    if config.max_expiry_duration.is_some() {
+        let expires_response: GetExpiresResponse =
+            deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: registrar_address.clone(),
+                msg: to_binary(&RegistrarQueryMsg::<WasmQuery>::GetExpires {
+                    id: token_id.clone(),
+                })?,
+            }))?;
+        validate_expiry(
+            &config,
+            &env,
+            expires_response.expires.saturating_add(duration),
+        )?;
+    }
+
+    let renew_registrar_message: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: registrar_address.clone(),
+        msg: to_binary(&RegistrarExecuteMsg::<Extension>::Renew {
+            id: token_id.clone(),
+            duration,
+        })?,
+        funds: vec![],
+    });
+    messages.push(renew_registrar_message);
+
+    Ok(messages)
+}
+
+pub fn owner_renew(
+    mut deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    name: String,
+    duration: u64,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    let name = canonical_name(&name)?;
+    let label = get_label_from_name(&name);
+    let token_id = get_token_id_from_label(&label);
+    let nodehash = get_nodehash(deps.as_ref(), label.clone())?;
+    let messages = _renew(deps.branch(), _env, info, token_id.clone(), duration)?;
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("method", "owner_renew")
+        .add_attribute("name", name)
+        .add_attribute("duration", duration.to_string())
+        .add_attribute("label", format!("{:?}", label))
+        .add_attribute("token_id", token_id)
+        .add_attribute("nodehash", format!("{:?}", nodehash)))
+}
+
+pub fn renew(
+    mut deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    duration: u64,
+    promo_code: Option<String>,
+) -> Result<Response, ContractError> {
+    let name = canonical_name(&name)?;
+    let discount = match &promo_code {
+        Some(code) => Some(redeem_promo_code(
+            deps.branch(),
+            &env,
+            code,
+            &name,
+            &info.sender,
+        )?),
+        None => None,
+    };
+    let (fund, refund) = validate_register_fund(
+        deps.as_ref(),
+        env.clone(),
+        info.clone(),
+        name.clone(),
+        duration,
+        discount.as_ref(),
+    )?;
+    let label = get_label_from_name(&name);
+    let token_id = get_token_id_from_label(&label);
+    let nodehash = get_nodehash(deps.as_ref(), label.clone())?;
+    let mut messages = _renew(deps.branch(), env, info.clone(), token_id.clone(), duration)?;
+    if let Some(refund_msg) = refund_excess(&info, &refund) {
+        messages.push(refund_msg);
+    }
+    messages.extend(split_register_revenue(deps.as_ref(), &fund)?);
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("method", "renew")
+        .add_attribute("name", name)
+        .add_attribute("payer", info.sender)
+        .add_attribute("duration", duration.to_string())
+        .add_attribute("label", format!("{:?}", label.clone()))
+        .add_attribute("token_id", token_id)
+        .add_attribute("nodehash", format!("{:?}", nodehash))
+        .add_attribute("refund", refund.amount))
+}
+
+fn validate_batch_size(size: usize) -> Result<(), ContractError> {
+    if size == 0 || size > MAX_BATCH_SIZE {
+        return Err(ContractError::BadRequest {
+            msg: format!("A batch must contain 1~{} names", MAX_BATCH_SIZE),
+        });
+    }
+    Ok(())
+}
+
+pub fn batch_register(
+    mut deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    registrations: Vec<RegisterRequest>,
+    skip_unavailable: bool,
+) -> Result<Response, ContractError> {
+    validate_enable_registration(deps.as_ref())?;
+    validate_batch_size(registrations.len())?;
+
+    // Price every name first so a single payment covers the whole batch
+    let config = CONFIG.load(deps.storage)?;
+    let denom = get_payment_denom(&config, &info)?;
+    let mut total = Uint128::zero();
+    let mut accepted: Vec<(RegisterRequest, Uint128)> = vec![];
+    let mut skipped: Vec<String> = vec![];
+    for registration in registrations {
+        let name = registration.name.clone();
+        let validation = if accepted.iter().any(|(other, _)| other.name == name) {
+            Err(ContractError::UnavailabledName {})
+        } else {
+            validate_name(deps.as_ref(), name.clone())
+        };
+        match validation {
+            Ok(()) => {}
+            Err(ContractError::UnavailabledName {} | ContractError::NameInAuction { .. })
+                if skip_unavailable =>
+            {
+                skipped.push(name);
+                continue;
+            }
+            Err(err) => return Err(err),
+        }
+        let (cost, _) = get_cost_in_denom(
+            deps.as_ref(),
+            &env,
+            &denom,
+            name,
+            registration.duration,
+            None,
+        )?;
+        total += cost;
+        accepted.push((registration, cost));
+    }
+    if accepted.is_empty() {
+        return Err(ContractError::BadRequest {
+            msg: String::from("No name in the batch is available"),
+        });
+    }
+
+    let fund = Coin {
+        denom,
+        amount: total,
+    };
+    let refund = validate_fund(&info, &fund)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut attributes: Vec<Attribute> = vec![];
+    for (registration, cost) in accepted {
+        let RegisterRequest {
+            name,
+            owner,
+            duration,
+            secret,
+            resolver,
+            address,
+            reverse_record,
+        } = registration;
+        let commitment = get_commitment(&name, &owner, &secret, &resolver, &address)?.commitment;
+        consume_commitment(deps.branch(), &env, commitment)?;
+        messages.extend(_register(
+            deps.branch(),
+            env.clone(),
+            name.clone(),
+            owner,
+            duration,
+            resolver,
+            address,
+            reverse_record,
+        )?);
+        attributes.push(Attribute::new("name", name));
+        attributes.push(Attribute::new("cost", cost));
+    }
+    for name in skipped {
+        attributes.push(Attribute::new("skipped", name));
+    }
+
+    if let Some(refund_msg) = refund_excess(&info, &refund) {
+        messages.push(refund_msg);
+    }
+    messages.extend(split_register_revenue(deps.as_ref(), &fund)?);
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("method", "batch_register")
+        .add_attributes(attributes)
+        .add_attribute("total", fund.amount)
+        .add_attribute("refund", refund.amount))
+}
+
+pub fn batch_renew(
+    mut deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    renewals: Vec<RenewRequest>,
+) -> Result<Response, ContractError> {
+    validate_batch_size(renewals.len())?;
+
+    // The expiry cap is checked against the stored expiry, so a name can't be renewed twice
+    let mut renewals = renewals;
+    let mut token_ids: Vec<String> = vec![];
+    for renewal in renewals.iter_mut() {
+        renewal.name = canonical_name(&renewal.name)?;
+        let token_id = get_token_id_from_label(&get_label_from_name(&renewal.name));
+        if token_ids.contains(&token_id) {
+            return Err(ContractError::BadRequest {
+                msg: format!("{} is renewed more than once", renewal.name),
+            });
+        }
+        token_ids.push(token_id);
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let denom = get_payment_denom(&config, &info)?;
+    let mut total = Uint128::zero();
+    let mut costs: Vec<Uint128> = vec![];
+    for renewal in renewals.iter() {
+        let (cost, _) = get_cost_in_denom(
+            deps.as_ref(),
+            &env,
+            &denom,
+            renewal.name.clone(),
+            renewal.duration,
+            None,
+        )?;
+        total += cost;
+        costs.push(cost);
+    }
+    let fund = Coin {
+        denom,
+        amount: total,
+    };
+    let refund = validate_fund(&info, &fund)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut attributes: Vec<Attribute> = vec![];
+    for ((renewal, cost), token_id) in renewals.into_iter().zip(costs).zip(token_ids) {
+        messages.extend(_renew(
+            deps.branch(),
+            env.clone(),
+            info.clone(),
+            token_id,
+            renewal.duration,
+        )?);
+        attributes.push(Attribute::new("name", renewal.name));
+        attributes.push(Attribute::new("duration", renewal.duration.to_string()));
+        attributes.push(Attribute::new("cost", cost));
+    }
+
+    if let Some(refund_msg) = refund_excess(&info, &refund) {
+        messages.push(refund_msg);
+    }
+    messages.extend(split_register_revenue(deps.as_ref(), &fund)?);
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("method", "batch_renew")
+        .add_attribute("payer", info.sender)
+        .add_attributes(attributes)
+        .add_attribute("total", fund.amount)
+        .add_attribute("refund", refund.amount))
+}
+
+fn load_auto_renew_config(storage: &dyn Storage) -> StdResult<AutoRenewConfig> {
+    Ok(AUTO_RENEW_CONFIG
+        .may_load(storage)?
+        .unwrap_or(AutoRenewConfig {
+            renew_window: DEFAULT_RENEW_WINDOW,
+            keeper_tip_bps: 0,
+        }))
+}
+
+fn only_name_owner(
+    deps: Deps<SeiQueryWrapper>,
+    info: &MessageInfo,
+    name: &String,
+) -> Result<(), ContractError> {
+    if get_name_owner(deps, name)? != info.sender {
+        return Err(ContractError::Unauthorized {
+            description: Some(format!("{} is not owned by {}", name, info.sender)),
+        });
+    }
+    Ok(())
+}
+
+fn update_auto_renew_escrow(
+    storage: &mut dyn Storage,
+    denom: &str,
+    deposit: Uint128,
+    release: Uint128,
+) -> StdResult<()> {
+    let escrow = AUTO_RENEW_ESCROW
+        .may_load(storage, denom.to_string())?
+        .unwrap_or_default();
+    AUTO_RENEW_ESCROW.save(storage, denom.to_string(), &(escrow + deposit - release))
+}
+
+// Clears the escrow of `name` and refunds it to its depositor
+fn release_auto_renew(
+    storage: &mut dyn Storage,
+    name: &str,
+    auto_renew: AutoRenew,
+) -> StdResult<CosmosMsg> {
+    AUTO_RENEWALS.remove(storage, name.to_string());
+    update_auto_renew_escrow(
+        storage,
+        &auto_renew.balance.denom,
+        Uint128::zero(),
+        auto_renew.balance.amount,
+    )?;
+    Ok(CosmosMsg::Bank(BankMsg::Send {
+        to_address: auto_renew.owner.to_string(),
+        amount: vec![auto_renew.balance],
+    }))
+}
+
+// Reports a renewal the crank could not make. The escrow of a reserved name or one at the
+// expiry cap is refunded like on an ownership change instead of being retried on every call
+fn skip_renewal(
+    storage: &mut dyn Storage,
+    name: &str,
+    auto_renew: AutoRenew,
+    err: ContractError,
+    messages: &mut Vec<CosmosMsg>,
+) -> StdResult<Attribute> {
+    if matches!(
+        err,
+        ContractError::ReservedName { .. } | ContractError::ExpiryTooFar { .. }
+    ) {
+        messages.push(release_auto_renew(storage, name, auto_renew)?);
+    }
+    Ok(Attribute::new("skipped", format!("{}: {}", name, err)))
+}
 
-            let reverse_registrar_address = deps
-                .api
-                .addr_humanize(&config.reverse_registrar_address)?
-                .to_string();
+pub fn set_auto_renew_config(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    auto_renew_config: AutoRenewConfig,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    if auto_renew_config.keeper_tip_bps > MAX_KEEPER_TIP_BPS {
+        return Err(ContractError::BadRequest {
+            msg: format!("keeper_tip_bps must be at most {}", MAX_KEEPER_TIP_BPS),
+        });
+    }
+    AUTO_RENEW_CONFIG.save(deps.storage, &auto_renew_config)?;
+    Ok(Response::new()
+        .add_attribute("method", "set_auto_renew_config")
+        .add_attribute("renew_window", auto_renew_config.renew_window.to_string())
+        .add_attribute(
+            "keeper_tip_bps",
+            auto_renew_config.keeper_tip_bps.to_string(),
+        ))
+}
 
-            let set_reverse_record_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: reverse_registrar_address,
-                msg: to_binary(&ReverseRegistrarExecuteMsg::SetNameForAddr {
-                    address: addr,
-                    owner,
-                    resolver,
-                    name: name + &".".to_string() + base_name.as_str(),
-                    // name: name + &".sei".to_string(),
-                })?,
-                funds: vec![],
+pub fn deposit_auto_renew(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    name: String,
+    duration: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let deposit = match info.funds.as_slice() {
+        [deposit] if !deposit.amount.is_zero() => deposit.clone(),
+        _ => {
+            return Err(ContractError::BadRequest {
+                msg: String::from("Deposit must be made in a single denom"),
+            })
+        }
+    };
+    if get_denom_price_source(&config, &deposit.denom).is_none() {
+        return Err(ContractError::UnexpectedDenom {
+            denom: deposit.denom,
+        });
+    }
+
+    let name = canonical_name(&name)?;
+    only_name_owner(deps.as_ref(), &info, &name)?;
+
+    // An escrow left by a previous owner of the name is refunded first
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut auto_renew = match AUTO_RENEWALS.may_load(deps.storage, name.clone())? {
+        Some(auto_renew) if auto_renew.owner != info.sender => {
+            messages.push(release_auto_renew(deps.storage, &name, auto_renew)?);
+            None
+        }
+        auto_renew => auto_renew,
+    }
+    .unwrap_or(AutoRenew {
+        balance: Coin {
+            denom: deposit.denom.clone(),
+            amount: Uint128::zero(),
+        },
+        duration: config.min_registration_duration,
+        owner: info.sender.clone(),
+    });
+    if auto_renew.balance.denom != deposit.denom {
+        return Err(ContractError::UnexpectedDenom {
+            denom: deposit.denom,
+        });
+    }
+    if let Some(duration) = duration {
+        if duration < config.min_registration_duration {
+            return Err(ContractError::DurationTooShort {
+                input_duration: duration,
+                min_duration: config.min_registration_duration,
             });
-            messages.push(set_reverse_record_msg);
         }
+        validate_max_duration(&config, duration)?;
+        auto_renew.duration = duration;
     }
+    auto_renew.balance.amount += deposit.amount;
 
-    Ok(messages)
+    AUTO_RENEWALS.save(deps.storage, name.clone(), &auto_renew)?;
+    update_auto_renew_escrow(
+        deps.storage,
+        &deposit.denom,
+        deposit.amount,
+        Uint128::zero(),
+    )?;
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("method", "deposit_auto_renew")
+        .add_attribute("name", name)
+        .add_attribute("depositor", info.sender)
+        .add_attribute("amount", deposit.amount)
+        .add_attribute("balance", auto_renew.balance.amount))
 }
 
-fn validate_register_fund(
-    deps: Deps<SeiQueryWrapper>,
+pub fn withdraw_auto_renew(
+    deps: DepsMut<SeiQueryWrapper>,
     _env: Env,
     info: MessageInfo,
     name: String,
-    duration: u64,
-) -> Result<Coin, ContractError> {
-    let cost: Uint128 = get_cost(deps, name.clone(), duration)?;
-    let base_fund = &Coin {
-        denom: String::from(REGISTER_FEE_DENOM),
-        amount: Uint128::from(0u128),
-    };
-    let fund = info
-        .funds
-        .iter()
-        .find(|fund| fund.denom == String::from(REGISTER_FEE_DENOM))
-        .unwrap_or(base_fund);
-    if fund.amount < cost {
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let name = canonical_name(&name)?;
+    let mut auto_renew =
+        AUTO_RENEWALS
+            .may_load(deps.storage, name.clone())?
+            .ok_or(ContractError::BadRequest {
+                msg: format!("{} has no auto-renew deposit", name),
+            })?;
+    if auto_renew.owner != info.sender {
+        return Err(ContractError::Unauthorized {
+            description: Some(format!(
+                "The auto-renew deposit of {} was not made by {}",
+                name, info.sender
+            )),
+        });
+    }
+    let amount = amount.unwrap_or(auto_renew.balance.amount);
+    if amount > auto_renew.balance.amount {
         return Err(ContractError::InsufficientFund {
-            amount: fund.amount,
-            required: cost,
+            amount: auto_renew.balance.amount,
+            required: amount,
         });
     }
 
-    Ok(fund.clone())
-}
-
-fn validate_enable_registration(deps: Deps<SeiQueryWrapper>) -> Result<(), ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    if !config.enable_registration {
-        return Err(ContractError::RegistrationDisabled {});
+    auto_renew.balance.amount -= amount;
+    if auto_renew.balance.amount.is_zero() {
+        AUTO_RENEWALS.remove(deps.storage, name.clone());
+    } else {
+        AUTO_RENEWALS.save(deps.storage, name.clone(), &auto_renew)?;
     }
-    Ok(())
+    update_auto_renew_escrow(
+        deps.storage,
+        &auto_renew.balance.denom,
+        Uint128::zero(),
+        amount,
+    )?;
+
+    let withdrawn = Coin {
+        denom: auto_renew.balance.denom,
+        amount,
+    };
+    Ok(Response::new()
+        .add_message(CosmosMsg::Bank(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![withdrawn],
+        }))
+        .add_attribute("method", "withdraw_auto_renew")
+        .add_attribute("name", name)
+        .add_attribute("amount", amount))
 }
 
-pub fn register(
+pub fn process_renewals(
     mut deps: DepsMut<SeiQueryWrapper>,
     env: Env,
     info: MessageInfo,
-    name: String,
-    owner: String,
-    duration: u64,
-    resolver: Option<String>,
-    address: Option<String>,
-    reverse_record: bool,
+    limit: Option<u32>,
 ) -> Result<Response, ContractError> {
-    validate_name(deps.as_ref(), name.clone())?;
-    validate_enable_registration(deps.as_ref())?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let config = CONFIG.load(deps.storage)?;
+    let auto_renew_config = load_auto_renew_config(deps.storage)?;
 
-    validate_register_fund(
-        deps.as_ref(),
-        env.clone(),
-        info,
-        name.clone(),
-        duration.clone(),
-    )?;
+    // Resume after the last processed name and wrap around once the end is reached
+    let cursor = AUTO_RENEW_CURSOR.may_load(deps.storage)?;
+    let mut auto_renewals = AUTO_RENEWALS
+        .range(
+            deps.storage,
+            cursor.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<(String, AutoRenew)>>>()?;
+    if auto_renewals.is_empty() {
+        auto_renewals = AUTO_RENEWALS
+            .range(deps.storage, None, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<(String, AutoRenew)>>>()?;
+    }
+    match auto_renewals.last() {
+        Some((name, _)) if auto_renewals.len() == limit => {
+            AUTO_RENEW_CURSOR.save(deps.storage, name)?
+        }
+        _ => AUTO_RENEW_CURSOR.remove(deps.storage),
+    }
 
-    let messages = _register(
-        deps.branch(),
-        env.clone(),
-        name.clone(),
-        owner,
-        duration,
-        resolver,
-        address,
-        reverse_record,
-    )?;
+    let registrar_address = deps
+        .api
+        .addr_humanize(&config.registrar_address)?
+        .to_string();
+    let grace_period_response: GetGracePeriodResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: registrar_address.clone(),
+            msg: to_binary(&RegistrarQueryMsg::<WasmQuery>::GetGracePeriod {})?,
+        }))?;
+    let current = env.block.time.seconds();
 
-    let label: Vec<u8> = get_label_from_name(&name);
-    let token_id = get_token_id_from_label(&label);
-    let nodehash = get_nodehash(deps.as_ref(), label.clone())?;
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut tips: Vec<Coin> = vec![];
+    let mut renewed: Vec<String> = vec![];
+    let mut skipped: Vec<Attribute> = vec![];
+    for (name, mut auto_renew) in auto_renewals {
+        // The escrow only renews the name for the owner that funded it
+        match get_name_owner(deps.as_ref(), &name) {
+            Ok(owner) if owner == auto_renew.owner => {}
+            _ => {
+                messages.push(release_auto_renew(deps.storage, &name, auto_renew)?);
+                continue;
+            }
+        }
+        let token_id = get_token_id_from_label(&get_label_from_name(&name));
+        let expires_response: GetExpiresResponse =
+            deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: registrar_address.clone(),
+                msg: to_binary(&RegistrarQueryMsg::<WasmQuery>::GetExpires {
+                    id: token_id.clone(),
+                })?,
+            }))?;
+        // Names are renewable until the end of their grace period, then the escrow is refunded
+        let expires = expires_response.expires;
+        if expires == 0 || expires + grace_period_response.grace_period < current {
+            messages.push(release_auto_renew(deps.storage, &name, auto_renew)?);
+            continue;
+        }
+        if expires > current + auto_renew_config.renew_window {
+            continue;
+        }
+
+        let denom = auto_renew.balance.denom.clone();
+        let cost = match get_cost_in_denom(
+            deps.as_ref(),
+            &env,
+            &denom,
+            name.clone(),
+            auto_renew.duration,
+            None,
+        ) {
+            Ok((cost, _)) => cost,
+            Err(err) => {
+                skipped.push(skip_renewal(
+                    deps.storage,
+                    &name,
+                    auto_renew,
+                    err,
+                    &mut messages,
+                )?);
+                continue;
+            }
+        };
+        let tip = cost.multiply_ratio(auto_renew_config.keeper_tip_bps, TOTAL_BPS);
+        if auto_renew.balance.amount < cost + tip {
+            let err = ContractError::InsufficientFund {
+                amount: auto_renew.balance.amount,
+                required: cost + tip,
+            };
+            skipped.push(skip_renewal(
+                deps.storage,
+                &name,
+                auto_renew,
+                err,
+                &mut messages,
+            )?);
+            continue;
+        }
+
+        // Failed renewals are skipped rather than failing the whole batch
+        let renew_messages = match _renew(
+            deps.branch(),
+            env.clone(),
+            info.clone(),
+            token_id,
+            auto_renew.duration,
+        ) {
+            Ok(renew_messages) => renew_messages,
+            Err(err) => {
+                skipped.push(skip_renewal(
+                    deps.storage,
+                    &name,
+                    auto_renew,
+                    err,
+                    &mut messages,
+                )?);
+                continue;
+            }
+        };
+
+        auto_renew.balance.amount -= cost + tip;
+        AUTO_RENEWALS.save(deps.storage, name.clone(), &auto_renew)?;
+        update_auto_renew_escrow(deps.storage, &denom, Uint128::zero(), cost + tip)?;
+        messages.extend(renew_messages);
+        messages.extend(split_register_revenue(
+            deps.as_ref(),
+            &Coin {
+                denom: denom.clone(),
+                amount: cost,
+            },
+        )?);
+        add_coin(&mut tips, &Coin { denom, amount: tip });
+        renewed.push(name);
+    }
 
+    if !tips.is_empty() {
+        messages.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: tips,
+        }));
+    }
+    let attributes: Vec<Attribute> = renewed
+        .iter()
+        .map(|name| Attribute::new("renewed", name))
+        .collect();
     Ok(Response::new()
         .add_messages(messages)
-        .add_attribute("method", "register")
-        .add_attribute("name", name)
-        .add_attribute("label", format!("{:?}", label.clone()))
-        .add_attribute("token_id", token_id)
-        .add_attribute("nodehash", format!("{:?}", nodehash)))
+        .add_attribute("method", "process_renewals")
+        .add_attribute("renewals", renewed.len().to_string())
+        .add_attributes(attributes)
+        .add_attributes(skipped))
 }
 
-pub fn referal_register(
-    mut deps: DepsMut<SeiQueryWrapper>,
+pub fn get_auto_renew(deps: Deps<SeiQueryWrapper>, name: String) -> StdResult<AutoRenewResponse> {
+    let name = normalize_label(&name).map_err(|err| StdError::generic_err(err.to_string()))?;
+    let auto_renew = AUTO_RENEWALS.may_load(deps.storage, name.clone())?;
+    Ok(AutoRenewResponse { name, auto_renew })
+}
+
+pub fn get_auto_renew_config(deps: Deps<SeiQueryWrapper>) -> StdResult<AutoRenewConfigResponse> {
+    let auto_renew_config = load_auto_renew_config(deps.storage)?;
+    Ok(AutoRenewConfigResponse { auto_renew_config })
+}
+
+pub fn start_auction(
+    deps: DepsMut<SeiQueryWrapper>,
     env: Env,
     info: MessageInfo,
     name: String,
-    owner: String,
+    reserve_price: Uint128,
+    min_increment: Uint128,
+    bidding_period: u64,
+    extension: u64,
     duration: u64,
-    resolver: Option<String>,
-    address: Option<String>,
-    referer_ensname: Option<String>,
-    reverse_record: bool,
 ) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
     validate_name(deps.as_ref(), name.clone())?;
-    validate_enable_registration(deps.as_ref())?;
-
-    let fund = validate_register_fund(
-        deps.as_ref(),
-        env.clone(),
-        info.clone(),
-        name.clone(),
-        duration.clone(),
-    )?;
+    let name = canonical_name(&name)?;
+    let config = CONFIG.load(deps.storage)?;
+    if duration < config.min_registration_duration {
+        return Err(ContractError::DurationTooShort {
+            input_duration: duration,
+            min_duration: config.min_registration_duration,
+        });
+    }
+    validate_max_duration(&config, duration)?;
+    if min_increment.is_zero() {
+        return Err(ContractError::BadRequest {
+            msg: String::from("min_increment must be greater than zero"),
+        });
+    }
+    if bidding_period == 0 {
+        return Err(ContractError::BadRequest {
+            msg: String::from("bidding_period must be greater than zero"),
+        });
+    }
 
-    let mut messages = _register(
-        deps.branch(),
-        env.clone(),
-        name.clone(),
-        owner.clone(),
+    let end_time = env.block.time.seconds() + bidding_period;
+    let auction = Auction {
+        reserve_price,
+        min_increment,
+        end_time,
+        extension,
         duration,
-        resolver,
-        address,
-        reverse_record,
-    )?;
+        highest_bid: None,
+    };
+    AUCTIONS.save(deps.storage, name.clone(), &auction)?;
+    Ok(Response::new()
+        .add_attribute("method", "start_auction")
+        .add_attribute("name", name)
+        .add_attribute("reserve_price", reserve_price)
+        .add_attribute("min_increment", min_increment)
+        .add_attribute("end_time", end_time.to_string())
+        .add_attribute("extension", extension.to_string())
+        .add_attribute("duration", duration.to_string()))
+}
 
-    let label: Vec<u8> = get_label_from_name(&name);
-    let token_id = get_token_id_from_label(&label);
-    let nodehash = get_nodehash(deps.as_ref(), label.clone());
+fn load_auction(deps: Deps<SeiQueryWrapper>, name: &str) -> Result<Auction, ContractError> {
+    AUCTIONS
+        .may_load(deps.storage, name.to_string())?
+        .ok_or(ContractError::AuctionNotFound {
+            name: name.to_string(),
+        })
+}
 
-    if let Some(referer) = referer_ensname {
-        let result = send_referal_funds(deps.as_ref(), env, info, &fund, referer);
+fn release_escrow(deps: DepsMut<SeiQueryWrapper>, bid: &Bid) -> Result<(), ContractError> {
+    AUCTION_ESCROW.update(deps.storage, |escrow| -> StdResult<_> {
+        Ok(escrow.checked_sub(bid.amount)?)
+    })?;
+    Ok(())
+}
+
+fn refund_bid(bid: &Bid) -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: bid.bidder.to_string(),
+        amount: vec![Coin {
+            denom: String::from(REGISTER_FEE_DENOM),
+            amount: bid.amount,
+        }],
+    })
+}
 
-        if let Ok(response) = result {
-            let referal_owner = response.1;
-            let referal_fund_amount = response.2;
-            let msg = response.0;
+pub fn cancel_auction(
+    mut deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    let name = canonical_name(&name)?;
+    let auction = load_auction(deps.as_ref(), &name)?;
+    AUCTIONS.remove(deps.storage, name.clone());
 
-            messages.push(cosmwasm_std::CosmosMsg::Bank(msg));
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if let Some(bid) = auction.highest_bid {
+        release_escrow(deps.branch(), &bid)?;
+        messages.push(refund_bid(&bid));
+    }
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("method", "cancel_auction")
+        .add_attribute("name", name))
+}
 
-            return Ok(Response::new()
-                .add_messages(messages)
-                .add_attribute("method", "register")
-                .add_attribute("name", name)
-                .add_attribute("label", format!("{:?}", label.clone()))
-                .add_attribute("token_id", token_id)
-                .add_attribute("nodehash", format!("{:?}", nodehash))
-                .add_attribute("referal_owner", referal_owner)
-                .add_attribute("referal_fund", referal_fund_amount));
+pub fn bid(
+    mut deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    let name = canonical_name(&name)?;
+    let mut auction = load_auction(deps.as_ref(), &name)?;
+    let current = env.block.time.seconds();
+    if current >= auction.end_time {
+        return Err(ContractError::AuctionEnded {
+            end_time: auction.end_time,
+            current,
+        });
+    }
+
+    let min_bid = match &auction.highest_bid {
+        Some(bid) => bid.amount + auction.min_increment,
+        None => auction.reserve_price,
+    };
+    let min_bid_coin = Coin {
+        denom: String::from(REGISTER_FEE_DENOM),
+        amount: min_bid,
+    };
+    let amount = match validate_fund(&info, &min_bid_coin) {
+        Ok(excess) => min_bid + excess.amount,
+        Err(ContractError::InsufficientFund { amount, .. }) => {
+            return Err(ContractError::BidTooLow { amount, min_bid })
         }
+        Err(err) => return Err(err),
+    };
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if let Some(outbid) = &auction.highest_bid {
+        release_escrow(deps.branch(), outbid)?;
+        messages.push(refund_bid(outbid));
+    }
+    let escrow = AUCTION_ESCROW.may_load(deps.storage)?.unwrap_or_default();
+    AUCTION_ESCROW.save(deps.storage, &(escrow + amount))?;
+
+    // Anti-sniping
+    if auction.end_time - current < auction.extension {
+        auction.end_time = current + auction.extension;
     }
+    auction.highest_bid = Some(Bid {
+        bidder: info.sender.clone(),
+        amount,
+    });
+    AUCTIONS.save(deps.storage, name.clone(), &auction)?;
 
     Ok(Response::new()
         .add_messages(messages)
-        .add_attribute("method", "register")
+        .add_attribute("method", "bid")
         .add_attribute("name", name)
-        .add_attribute("label", format!("{:?}", label.clone()))
-        .add_attribute("token_id", token_id)
-        .add_attribute("nodehash", format!("{:?}", nodehash)))
+        .add_attribute("bidder", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("end_time", auction.end_time.to_string()))
 }
 
-pub fn send_referal_funds(
-    deps: Deps<SeiQueryWrapper>,
-    _env: Env,
+pub fn settle_auction(
+    mut deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
     _info: MessageInfo,
-    fund: &Coin,
-    referer_ensname: String,
-) -> Result<(BankMsg, String, Uint128), ContractError>{
-    let config = CONFIG.load(deps.storage)?;
-    let registrar_address = deps
-        .api
-        .addr_humanize(&config.registrar_address)?
-        .to_string();
-
-    let label = get_label_from_name(&referer_ensname);
-    let nodehash = get_nodehash(deps, label)?;
-    let get_registry_response: GetRegistryResponse =
-        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-            contract_addr: registrar_address.clone(),
-            msg: to_binary(&RegistrarQueryMsg::<WasmQuery>::GetRegistry {})?,
-        }))?;
-    let registry_address = String::from(get_registry_response.registry);
+    name: String,
+) -> Result<Response, ContractError> {
+    let name = canonical_name(&name)?;
+    let auction = load_auction(deps.as_ref(), &name)?;
+    let current = env.block.time.seconds();
+    if current < auction.end_time {
+        return Err(ContractError::AuctionNotEnded {
+            end_time: auction.end_time,
+            current,
+        });
+    }
+    AUCTIONS.remove(deps.storage, name.clone());
 
-    let get_record_by_node_response: RecordResponse =
-        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-            contract_addr: registry_address.clone(),
-            msg: to_binary(&RegistryQueryMsg::GetRecordByNode {
-                node: nodehash.clone(),
-            })?,
-        }))?;
+    let bid = match auction.highest_bid {
+        Some(bid) => bid,
+        None => {
+            return Ok(Response::new()
+                .add_attribute("method", "settle_auction")
+                .add_attribute("name", name))
+        }
+    };
+    // The winning bid becomes proceeds
+    release_escrow(deps.branch(), &bid)?;
+    let mut messages = _register(
+        deps.branch(),
+        env,
+        name.clone(),
+        bid.bidder.to_string(),
+        auction.duration,
+        None,
+        None,
+        false,
+    )?;
+    let revenue = Coin {
+        denom: String::from(REGISTER_FEE_DENOM),
+        amount: bid.amount,
+    };
+    messages.extend(split_register_revenue(deps.as_ref(), &revenue)?);
 
-    let referal_owner = get_record_by_node_response.owner;
+    let label: Vec<u8> = get_label_from_name(&name);
+    let token_id = get_token_id_from_label(&label);
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("method", "settle_auction")
+        .add_attribute("name", name)
+        .add_attribute("winner", bid.bidder)
+        .add_attribute("amount", bid.amount)
+        .add_attribute("token_id", token_id))
+}
 
-    let mut referal_fund = fund.clone();
-    referal_fund.amount = fund
-        .amount
-        .multiply_ratio(config.referal_percentage.0 as u128, 100u128);
+pub fn get_auction(deps: Deps<SeiQueryWrapper>, name: String) -> StdResult<AuctionResponse> {
+    let name = normalize_label(&name).map_err(|err| StdError::generic_err(err.to_string()))?;
+    let auction = AUCTIONS.load(deps.storage, name.clone())?;
+    Ok(AuctionResponse { name, auction })
+}
 
-    let result = is_whitelisted_account(deps, referer_ensname);
+pub fn get_auctions(
+    deps: Deps<SeiQueryWrapper>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AuctionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let auctions = AUCTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(name, auction)| AuctionResponse { name, auction }))
+        .collect::<StdResult<Vec<AuctionResponse>>>()?;
+    Ok(AuctionsResponse { auctions })
+}
 
-    if result.0 {
-        referal_fund.amount = fund
-            .amount
-            .multiply_ratio(result.2 as u128, 100u128);
+pub fn set_cw20_token(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    token: String,
+    rate: Decimal,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    if rate.is_zero() {
+        return Err(ContractError::BadRequest {
+            msg: String::from("rate must be greater than zero"),
+        });
     }
+    let token = deps.api.addr_validate(&token)?;
+    CW20_TOKENS.save(deps.storage, &token, &rate)?;
+    Ok(Response::new()
+        .add_attribute("method", "set_cw20_token")
+        .add_attribute("token", token)
+        .add_attribute("rate", rate.to_string()))
+}
 
-    let amount = referal_fund.amount;
+// Withdraw only covers accepted tokens, so the balance is swept to the treasury on removal
+pub fn remove_cw20_token(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    token: String,
+) -> Result<Response, ContractError> {
+    only_owner(deps.as_ref(), &info)?;
+    let token = deps.api.addr_validate(&token)?;
+    if !CW20_TOKENS.has(deps.storage, &token) {
+        return Err(ContractError::UnsupportedToken {
+            token: token.to_string(),
+        });
+    }
+    CW20_TOKENS.remove(deps.storage, &token);
 
-    let bank_msg = BankMsg::Send {
-        to_address: referal_owner.to_string(),
-        amount: vec![referal_fund],
-    };
+    let config = CONFIG.load(deps.storage)?;
+    let treasury = deps.api.addr_humanize(&config.treasury)?;
+    let amount = get_cw20_balance(deps.as_ref(), &env, &token)?;
+    let message = transfer_cw20(&token, &treasury, amount)?;
+    Ok(Response::new()
+        .add_messages(message)
+        .add_attribute("method", "remove_cw20_token")
+        .add_attribute("token", token)
+        .add_attribute("amount", amount))
+}
 
-    // Ok(Response::new()
-    //     .add_attribute("owner", referal_owner)
-    //     .add_attribute("fund", amount))
-    Ok((bank_msg, referal_owner.to_string(), amount))
+pub fn get_cw20_tokens(
+    deps: Deps<SeiQueryWrapper>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Cw20TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|token| deps.api.addr_validate(&token))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+    let tokens = CW20_TOKENS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(token, rate)| Cw20TokenInfo { token, rate }))
+        .collect::<StdResult<Vec<Cw20TokenInfo>>>()?;
+    Ok(Cw20TokensResponse { tokens })
 }
 
-pub fn is_whitelisted_account(deps: Deps<SeiQueryWrapper>, ensname: String) -> (bool, Vec<u8>, u32) {
-    let account = WHITELIST.load(deps.storage, ensname.clone());
+fn transfer_cw20(
+    token: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<Option<CosmosMsg>> {
+    if amount.is_zero() {
+        return Ok(None);
+    }
+    Ok(Some(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    })))
+}
 
-    match account {
-        Ok(val) => {
-            return (true, val.0, val.1);
+pub fn receive_cw20(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // The CW20 contract is the message sender
+    let token = info.sender;
+    let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+    match from_binary(&cw20_msg.msg)? {
+        ReceiveMsg::Register {
+            name,
+            owner,
+            duration,
+            secret,
+            resolver,
+            address,
+            reverse_record,
+        } => cw20_register(
+            deps,
+            env,
+            token,
+            sender,
+            cw20_msg.amount,
+            name,
+            owner,
+            duration,
+            secret,
+            resolver,
+            address,
+            reverse_record,
+        ),
+        ReceiveMsg::Renew { name, duration } => {
+            cw20_renew(deps, env, token, sender, cw20_msg.amount, name, duration)
         }
-        Err(_) => return (false, vec![], 0),
     }
 }
 
-pub fn owner_register(
+fn validate_cw20_fund(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    token: &Addr,
+    amount: Uint128,
+    name: String,
+    duration: u64,
+) -> Result<Uint128, ContractError> {
+    let cost = get_cw20_cost(deps, env, token, name, duration)?;
+    if amount < cost {
+        return Err(ContractError::InsufficientFund {
+            amount,
+            required: cost,
+        });
+    }
+    Ok(amount - cost)
+}
+
+fn cw20_register(
     mut deps: DepsMut<SeiQueryWrapper>,
     env: Env,
-    info: MessageInfo,
+    token: Addr,
+    sender: Addr,
+    amount: Uint128,
     name: String,
     owner: String,
     duration: u64,
+    secret: String,
     resolver: Option<String>,
     address: Option<String>,
     reverse_record: bool,
 ) -> Result<Response, ContractError> {
-    only_owner(deps.as_ref(), &info)?;
+    validate_name(deps.as_ref(), name.clone())?;
+    validate_enable_registration(deps.as_ref())?;
+    let refund = validate_cw20_fund(deps.as_ref(), &env, &token, amount, name.clone(), duration)?;
 
-    if !is_available_name(deps.as_ref(), &name)? {
-        return Err(ContractError::UnavailabledName {});
-    }
+    let commitment = get_commitment(&name, &owner, &secret, &resolver, &address)?.commitment;
+    consume_commitment(deps.branch(), &env, commitment)?;
 
-    let messages = _register(
+    let mut messages = _register(
         deps.branch(),
-        env.clone(),
+        env,
         name.clone(),
         owner,
         duration,
@@ -664,6 +3797,12 @@ pub fn owner_register(
         address,
         reverse_record,
     )?;
+    if let Some(refund_msg) = transfer_cw20(&token, &sender, refund)? {
+        messages.push(refund_msg);
+    }
+    if let Some(fee_split) = get_register_fee_split(deps.as_ref())? {
+        messages.extend(split_cw20(&fee_split, &token, amount - refund)?);
+    }
 
     let label: Vec<u8> = get_label_from_name(&name);
     let token_id = get_token_id_from_label(&label);
@@ -671,113 +3810,76 @@ pub fn owner_register(
 
     Ok(Response::new()
         .add_messages(messages)
-        .add_attribute("method", "owner_register")
-        .add_attribute("name", name)
-        .add_attribute("label", format!("{:?}", label.clone()))
-        .add_attribute("token_id", token_id)
-        .add_attribute("nodehash", format!("{:?}", nodehash)))
-}
-
-fn _renew(
-    deps: DepsMut<SeiQueryWrapper>,
-    _env: Env,
-    _info: MessageInfo,
-    token_id: String,
-    duration: u64,
-) -> Result<Vec<CosmosMsg>, ContractError> {
-    let mut messages: Vec<CosmosMsg> = vec![];
-    let config = CONFIG.load(deps.storage)?;
-    let registrar_address = deps
-        .api
-        .addr_humanize(&config.registrar_address)?
-        .to_string();
-
-    let renew_registrar_message: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: registrar_address.clone(),
-        msg: to_binary(&RegistrarExecuteMsg::<Extension>::Renew {
-            id: token_id.clone(),
-            duration,
-        })?,
-        funds: vec![],
-    });
-    messages.push(renew_registrar_message);
-
-    Ok(messages)
-}
-
-pub fn owner_renew(
-    mut deps: DepsMut<SeiQueryWrapper>,
-    _env: Env,
-    info: MessageInfo,
-    name: String,
-    duration: u64,
-) -> Result<Response, ContractError> {
-    only_owner(deps.as_ref(), &info)?;
-    let label = get_label_from_name(&name);
-    let token_id = get_token_id_from_label(&label);
-    let nodehash = get_nodehash(deps.as_ref(), label.clone())?;
-    let messages = _renew(deps.branch(), _env, info, token_id.clone(), duration)?;
-    Ok(Response::new()
-        .add_messages(messages)
-        .add_attribute("method", "owner_renew")
+        .add_attribute("method", "cw20_register")
         .add_attribute("name", name)
-        .add_attribute("duration", duration.to_string())
         .add_attribute("label", format!("{:?}", label))
         .add_attribute("token_id", token_id)
-        .add_attribute("nodehash", format!("{:?}", nodehash)))
+        .add_attribute("nodehash", format!("{:?}", nodehash))
+        .add_attribute("token", token)
+        .add_attribute("refund", refund))
 }
 
-pub fn renew(
+fn cw20_renew(
     mut deps: DepsMut<SeiQueryWrapper>,
     env: Env,
-    info: MessageInfo,
+    token: Addr,
+    sender: Addr,
+    amount: Uint128,
     name: String,
     duration: u64,
 ) -> Result<Response, ContractError> {
-    validate_register_fund(
-        deps.as_ref(),
-        env.clone(),
-        info.clone(),
-        name.clone(),
-        duration,
-    )?;
+    let name = canonical_name(&name)?;
+    let refund = validate_cw20_fund(deps.as_ref(), &env, &token, amount, name.clone(), duration)?;
     let label = get_label_from_name(&name);
     let token_id = get_token_id_from_label(&label);
     let nodehash = get_nodehash(deps.as_ref(), label.clone())?;
-    let messages = _renew(deps.branch(), env, info, token_id.clone(), duration)?;
+    let info = MessageInfo {
+        sender: sender.clone(),
+        funds: vec![],
+    };
+    let mut messages = _renew(deps.branch(), env, info, token_id.clone(), duration)?;
+    if let Some(refund_msg) = transfer_cw20(&token, &sender, refund)? {
+        messages.push(refund_msg);
+    }
+    if let Some(fee_split) = get_register_fee_split(deps.as_ref())? {
+        messages.extend(split_cw20(&fee_split, &token, amount - refund)?);
+    }
     Ok(Response::new()
         .add_messages(messages)
-        .add_attribute("method", "renew")
+        .add_attribute("method", "cw20_renew")
         .add_attribute("name", name)
+        .add_attribute("payer", sender)
         .add_attribute("duration", duration.to_string())
-        .add_attribute("label", format!("{:?}", label.clone()))
+        .add_attribute("label", format!("{:?}", label))
         .add_attribute("token_id", token_id)
-        .add_attribute("nodehash", format!("{:?}", nodehash)))
+        .add_attribute("nodehash", format!("{:?}", nodehash))
+        .add_attribute("token", token)
+        .add_attribute("refund", refund))
 }
 
-// pub fn get_commitment(
-//     name: &String,
-//     owner: &String,
-//     secret: &String,
-//     resolver: &Option<String>,
-//     address: &Option<String>,
-// ) -> StdResult<GetCommitmentResponse> {
-//     let label = get_label_from_name(name);
-
-//     let arr = [
-//         &label[..],
-//         owner.as_bytes(),
-//         resolver.as_deref().unwrap_or(&String::from("")).as_bytes(),
-//         address.as_deref().unwrap_or(&String::from("")).as_bytes(),
-//         secret.as_bytes(),
-//     ]
-//     .concat();
-
-//     let commitment_vec = keccak256(&arr);
-//     Ok(GetCommitmentResponse {
-//         commitment: hex::encode(commitment_vec),
-//     })
-// }
+pub fn get_commitment(
+    name: &String,
+    owner: &String,
+    secret: &String,
+    resolver: &Option<String>,
+    address: &Option<String>,
+) -> StdResult<MakeCommitmentResponse> {
+    let label = get_label_from_name(name);
+
+    let arr = [
+        &label[..],
+        owner.as_bytes(),
+        resolver.as_deref().unwrap_or("").as_bytes(),
+        address.as_deref().unwrap_or("").as_bytes(),
+        secret.as_bytes(),
+    ]
+    .concat();
+
+    let commitment_vec = keccak256(&arr);
+    Ok(MakeCommitmentResponse {
+        commitment: hex::encode(commitment_vec),
+    })
+}
 
 pub fn get_nodehash(deps: Deps<SeiQueryWrapper>, label: Vec<u8>) -> StdResult<Vec<u8>> {
     let config = CONFIG.load(deps.storage)?;
@@ -812,7 +3914,7 @@ pub fn is_available_name(deps: Deps<SeiQueryWrapper>, name: &String) -> StdResul
             contract_addr: registrar_address,
             msg: to_binary(&RegistrarQueryMsg::<WasmQuery>::IsAvailable { id })?,
         }))?;
-    return Ok(is_available_response.available);
+    Ok(is_available_response.available)
 }
 
 pub fn get_owner(deps: Deps<SeiQueryWrapper>) -> StdResult<OwnerResponse> {
@@ -827,23 +3929,112 @@ pub fn get_registrar(deps: Deps<SeiQueryWrapper>) -> StdResult<RegistrarResponse
     Ok(RegistrarResponse { registrar_address })
 }
 
-pub fn get_rent_price(deps: Deps<SeiQueryWrapper>, name: String, duration: u64) -> StdResult<RentPriceResponse> {
-    let cost = get_cost(deps, name, duration);
-    if let Err(_err) = cost {
-        return Err(StdError::generic_err("error"));
+pub fn get_rent_price(
+    deps: Deps<SeiQueryWrapper>,
+    env: Env,
+    name: String,
+    duration: u64,
+    promo_code: Option<String>,
+) -> StdResult<RentPriceResponse> {
+    // Surface why the name can't be priced instead of a bare query failure
+    get_rent_price_breakdown(deps, &env, name, duration, promo_code).map_err(|err| match err {
+        ContractError::Std(err) => err,
+        err => StdError::generic_err(err.to_string()),
+    })
+}
+
+fn get_rent_price_breakdown(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    name: String,
+    duration: u64,
+    promo_code: Option<String>,
+) -> Result<RentPriceResponse, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let breakdown = get_price_breakdown(deps, env, &config, name.clone(), duration)?;
+    let exchange_rate = get_exchange_rate(deps, env, REGISTER_FEE_DENOM, &config.price_source)?;
+    let discount = match &promo_code {
+        Some(code) => Some(check_promo_code(deps, env, code, &name)?.1.discount),
+        None => None,
+    };
+    let (full_price, _) =
+        get_cost_in_denom(deps, env, REGISTER_FEE_DENOM, name.clone(), duration, None)?;
+    let (price, premium) = get_cost_in_denom(
+        deps,
+        env,
+        REGISTER_FEE_DENOM,
+        name.clone(),
+        duration,
+        discount.as_ref(),
+    )?;
+    let base_price = to_denom_amount(breakdown.base, exchange_rate);
+    let duration_discount =
+        base_price - to_denom_amount(breakdown.base - breakdown.duration_discount, exchange_rate);
+
+    // Denoms without a usable rate are left out
+    let mut quotes = vec![Coin {
+        denom: String::from(REGISTER_FEE_DENOM),
+        amount: price,
+    }];
+    for accepted in config.accepted_denoms.iter() {
+        if let Ok((amount, _)) = get_cost_in_denom(
+            deps,
+            env,
+            &accepted.denom,
+            name.clone(),
+            duration,
+            discount.as_ref(),
+        ) {
+            quotes.push(Coin {
+                denom: accepted.denom.clone(),
+                amount,
+            });
+        }
     }
     Ok(RentPriceResponse {
-        price: cost.unwrap(),
+        price,
+        denom: String::from(REGISTER_FEE_DENOM),
+        duration,
+        annual_price: breakdown.annual_price,
+        exchange_rate,
+        base_price,
+        duration_discount,
+        premium,
+        discount: full_price - price,
+        quotes,
+    })
+}
+
+pub fn get_commitment_timestamp(
+    deps: Deps<SeiQueryWrapper>,
+    commitment: String,
+) -> StdResult<CommitmentTimestampResponse> {
+    let timestamp = COMMITMENTS.load(deps.storage, commitment)?;
+    Ok(CommitmentTimestampResponse { timestamp })
+}
+
+pub fn get_max_commitment_age(deps: Deps<SeiQueryWrapper>) -> StdResult<MaxCommitmentAgeResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(MaxCommitmentAgeResponse {
+        age: config.max_commitment_age,
+    })
+}
+
+pub fn get_min_commitment_age(deps: Deps<SeiQueryWrapper>) -> StdResult<MinCommitmentAgeResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(MinCommitmentAgeResponse {
+        age: config.min_commitment_age,
     })
 }
 
-// pub fn get_commitment_timestamp(
-//     deps: Deps,
-//     commitment: String,
-// ) -> StdResult<CommitmentTimestampResponse> {
-//     let timestamp = COMMITMENTS.load(deps.storage, commitment)?;
-//     Ok(CommitmentTimestampResponse { timestamp })
-// }
+pub fn get_duration_limits(deps: Deps<SeiQueryWrapper>) -> StdResult<DurationLimitsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(DurationLimitsResponse {
+        min_registration_duration: config.min_registration_duration,
+        max_registration_duration: config.max_registration_duration,
+        max_expiry_duration: config.max_expiry_duration,
+    })
+}
 
 pub fn get_min_registration_duration(deps: Deps<SeiQueryWrapper>) -> StdResult<MinRegistrationDurationResponse> {
     let config = CONFIG.load(deps.storage)?;
@@ -852,28 +4043,39 @@ pub fn get_min_registration_duration(deps: Deps<SeiQueryWrapper>) -> StdResult<M
     })
 }
 
-pub fn get_is_valid_name(name: &String) -> StdResult<IsValidNameResponse> {
-    let graphemes = name.graphemes(true).collect::<Vec<&str>>();
-    let name_length = graphemes.len();
-    if graphemes[0usize] == "-" {
-        return Ok(IsValidNameResponse {
-            is_valid_name: false,
-        });
-    }
-    let is_valid_name = name_length >= 3
-        && name.chars().all(|c| -> bool {
-            match c {
-                '0'..='9' => true,
-                'a'..='z' => true,
-                '-' => true,
-                _c => false,
-            }
-        });
-    Ok(IsValidNameResponse { is_valid_name })
+pub fn get_is_valid_name(
+    deps: Deps<SeiQueryWrapper>,
+    name: &str,
+) -> StdResult<IsValidNameResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let canonical = normalize_label(name).unwrap_or_else(|_| name.to_string());
+    let is_reserved = matches!(
+        PREMIUM_NAMES.may_load(deps.storage, canonical)?,
+        Some(PremiumName::Reserved {})
+    );
+    // The shortest priced length is the shortest registrable length
+    let min_length = load_price_tiers(deps.storage)?
+        .first()
+        .map_or(u32::MAX, |tier| tier.min_length);
+    let failed_rule = match validate_label(name, &config.name_policy) {
+        Err(rule) => Some(rule),
+        Ok(()) if (name.graphemes(true).count() as u32) < min_length => {
+            Some(NameRule::MinLength { min_length })
+        }
+        Ok(()) => None,
+    };
+    Ok(IsValidNameResponse {
+        is_valid_name: failed_rule.is_none(),
+        is_reserved,
+        failed_rule,
+    })
 }
 
-pub fn get_node_info_from_name(deps: Deps<SeiQueryWrapper>, name: &String) -> StdResult<NodeInfoResponse> {
-    let label: Vec<u8> = get_label_from_name(&name);
+pub fn get_node_info_from_name(
+    deps: Deps<SeiQueryWrapper>,
+    name: &String,
+) -> StdResult<NodeInfoResponse> {
+    let label: Vec<u8> = get_label_from_name(name);
     let token_id = get_token_id_from_label(&label);
     let node = get_nodehash(deps, label.clone())?;
     Ok(NodeInfoResponse {
@@ -884,46 +4086,34 @@ pub fn get_node_info_from_name(deps: Deps<SeiQueryWrapper>, name: &String) -> St
 }
 
 pub fn get_token_id_from_name(name: &String) -> StdResult<TokenIdResponse> {
-    let label: Vec<u8> = get_label_from_name(&name);
+    let label: Vec<u8> = get_label_from_name(name);
     let token_id = get_token_id_from_label(&label);
     Ok(TokenIdResponse { token_id })
 }
 
 pub fn get_nodehash_from_name(deps: Deps<SeiQueryWrapper>, name: &String) -> StdResult<NodehashResponse> {
-    let label: Vec<u8> = get_label_from_name(&name);
+    let label: Vec<u8> = get_label_from_name(name);
     let node = get_nodehash(deps, label)?;
     Ok(NodehashResponse { node })
 }
 
-fn validate_whitelist_fund(deps: Deps<SeiQueryWrapper>, _env: Env, info: MessageInfo) -> Result<(), ContractError> {
+fn validate_whitelist_fund(deps: Deps<SeiQueryWrapper>, _env: Env, info: MessageInfo) -> Result<Coin, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let cost = Uint128::from(config.whitelist_price);
-    let base_fund = &Coin {
+    let cost = Coin {
         denom: String::from(REGISTER_FEE_DENOM),
-        amount: Uint128::from(0u128),
+        amount: Uint128::from(config.whitelist_price),
     };
-    let fund = info
-        .funds
-        .iter()
-        .find(|fund| fund.denom == String::from(REGISTER_FEE_DENOM))
-        .unwrap_or(base_fund);
-    if fund.amount < cost {
-        return Err(ContractError::InsufficientFund {
-            amount: fund.amount,
-            required: cost,
-        });
-    }
-
-    Ok(())
+    validate_fund(&info, &cost)
 }
 
 pub fn add_whitelist(
     deps: DepsMut<SeiQueryWrapper>,
     env: Env,
     info: MessageInfo,
-    ensname: &String,
+    ensname: &str,
 ) -> Result<Response, ContractError> {
-    validate_whitelist_fund(deps.as_ref(), env, info)?;
+    let ensname = &canonical_name(ensname)?;
+    let refund = validate_whitelist_fund(deps.as_ref(), env, info.clone())?;
 
     let config = CONFIG.load(deps.storage)?;
     let registrar_address = deps
@@ -950,25 +4140,41 @@ pub fn add_whitelist(
             })?,
         }))?;
 
+    let owner = get_record_by_node_response.owner;
+    if owner != info.sender {
+        return Err(ContractError::Unauthorized {
+            description: Some(format!("{} is not owned by {}", ensname, info.sender)),
+        });
+    }
+
     WHITELIST.save(
         deps.storage,
         ensname.clone(),
-        &(nodehash, config.referal_percentage.1),
+        &WhitelistEntry {
+            node: nodehash,
+            owner: owner.clone(),
+            percentage: config.referal_percentage.1,
+            expires: None,
+        },
     )?;
     Ok(Response::new()
+        .add_messages(refund_excess(&info, &refund))
         .add_attribute("method", "add_white_list")
         .add_attribute("ensname", ensname)
-        .add_attribute("owner", get_record_by_node_response.owner))
+        .add_attribute("owner", owner)
+        .add_attribute("refund", refund.amount))
 }
 
 pub fn add_whitelist_by_owner(
     deps: DepsMut<SeiQueryWrapper>,
     _env: Env,
     info: MessageInfo,
-    ensname: &String,
+    ensname: &str,
     refereal_percentage: Option<u32>,
+    expires: Option<u64>,
 ) -> Result<Response, ContractError> {
     only_owner(deps.as_ref(), &info)?;
+    let ensname = &canonical_name(ensname)?;
 
     if let Some(referal_precent_value) = refereal_percentage {
         if referal_precent_value > 50u32 {
@@ -1003,18 +4209,16 @@ pub fn add_whitelist_by_owner(
             })?,
         }))?;
 
-    match refereal_percentage {
-        Some(value) => {
-            WHITELIST.save(deps.storage, ensname.clone(), &(nodehash, value))?;
-        }
-        None => {
-            WHITELIST.save(
-                deps.storage,
-                ensname.clone(),
-                &(nodehash, config.referal_percentage.1),
-            )?;
-        }
-    }
+    WHITELIST.save(
+        deps.storage,
+        ensname.clone(),
+        &WhitelistEntry {
+            node: nodehash,
+            owner: get_record_by_node_response.owner.clone(),
+            percentage: refereal_percentage.unwrap_or(config.referal_percentage.1),
+            expires,
+        },
+    )?;
 
     Ok(Response::new()
         .add_attribute("method", "add_white_list")
@@ -1022,42 +4226,44 @@ pub fn add_whitelist_by_owner(
         .add_attribute("owner", get_record_by_node_response.owner))
 }
 
-fn set_reverse_record(
-    deps: DepsMut,
-    name: String,
-    address: String,
-    resolver: Option<String>,
-    owner: String,
+pub fn remove_whitelist(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    info: MessageInfo,
+    ensname: String,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    let registrar_address = deps
-        .api
-        .addr_humanize(&config.registrar_address)?
-        .to_string();
-
-    let get_registrar_config_response: ConfigResponse =
-        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-            contract_addr: registrar_address.clone(),
-            msg: to_binary(&RegistrarQueryMsg::<WasmQuery>::GetConfig {})?,
-        }))?;
-
-    let base_name = get_registrar_config_response.base_name;
-
-    let reverse_registrar_address = deps
-        .api
-        .addr_humanize(&config.reverse_registrar_address)?
-        .to_string();
+    only_owner(deps.as_ref(), &info)?;
+    let ensname = canonical_name(&ensname)?;
+    if !WHITELIST.has(deps.storage, ensname.clone()) {
+        return Err(ContractError::BadRequest {
+            msg: format!("{} is not whitelisted", ensname),
+        });
+    }
+    WHITELIST.remove(deps.storage, ensname.clone());
+    Ok(Response::new()
+        .add_attribute("method", "remove_white_list")
+        .add_attribute("ensname", ensname))
+}
 
-    let _set_reverse_record_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: reverse_registrar_address,
-        msg: to_binary(&ReverseRegistrarExecuteMsg::SetNameForAddr {
-            address,
-            owner,
-            resolver,
-            name: name + &".".to_string() + base_name.as_str(),
-            // name: name + &".sei".to_string(),
-        })?,
-        funds: vec![],
-    });
-    Ok(Response::default())
+pub fn get_whitelist(
+    deps: Deps<SeiQueryWrapper>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<WhiteListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let entries = WHITELIST
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(name, entry)| WhiteListInfo {
+                name,
+                node: entry.node,
+                owner: entry.owner,
+                percentage: entry.percentage,
+                expires: entry.expires,
+            })
+        })
+        .collect::<StdResult<Vec<WhiteListInfo>>>()?;
+    Ok(WhiteListResponse { entries })
 }
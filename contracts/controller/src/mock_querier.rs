@@ -1,17 +1,27 @@
-use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use crate::msg::{PriceFeedQueryMsg, PriceFeedResponse};
+use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_binary, from_slice, to_binary, Addr, Coin, ContractResult, Empty, OwnedDeps, Querier,
-    QuerierResult, QueryRequest, SystemError, SystemResult, WasmQuery,
+    from_binary, from_slice, to_binary, Addr, Coin, ContractResult, Decimal, Empty, OwnedDeps,
+    Querier, QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, Uint64, WasmQuery,
 };
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg};
 use dotlabs::registrar::{
-    GetBaseNodeResponse, GetRegistryResponse, IsAvailableResponse, QueryMsg as RegistrarQueryMsg,
+    GetBaseNodeResponse, GetExpiresResponse, GetGracePeriodResponse, GetRegistryResponse,
+    IsAvailableResponse, QueryMsg as RegistrarQueryMsg,
 };
-use sei_cosmwasm::SeiQueryWrapper;
+use dotlabs::registry::{QueryMsg as RegistryQueryMsg, RecordResponse};
+use dotlabs::utils::{get_label_from_name, keccak256};
+use sei_cosmwasm::{
+    DenomOracleExchangeRatePair, ExchangeRatesResponse, OracleExchangeRate, SeiQuery,
+    SeiQueryWrapper,
+};
+pub const BASE_NODE: &str = "749f2b479b45e5da8e4cbecd926ee9a6f78db5424fa6993b6ecababa5d736b12";
+
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
 /// this uses our CustomQuerier.
 pub fn mock_dependencies(
     contract_balance: &[Coin],
-) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier, SeiQueryWrapper> {
     let custom_querier: WasmMockQuerier =
         WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
 
@@ -71,6 +81,59 @@ impl Querier for WasmMockQuerier {
 impl WasmMockQuerier {
     pub fn handle_query(&self, request: &QueryRequest<SeiQueryWrapper>) -> QuerierResult {
         match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if contract_addr == "price_feed_address" =>
+            {
+                match from_binary::<PriceFeedQueryMsg>(msg) {
+                    Ok(PriceFeedQueryMsg::Price { .. }) => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&PriceFeedResponse {
+                            rate: Decimal::percent(50),
+                            last_updated: mock_env().block.time.seconds(),
+                        })
+                        .unwrap(),
+                    )),
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if contract_addr == "cw20_token_address" =>
+            {
+                match from_binary::<Cw20QueryMsg>(msg) {
+                    Ok(Cw20QueryMsg::Balance { .. }) => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&Cw20BalanceResponse {
+                            balance: Uint128::from(1_000_000u128),
+                        })
+                        .unwrap(),
+                    )),
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if contract_addr == "registry_address" =>
+            {
+                match from_binary::<RegistryQueryMsg>(msg) {
+                    // Every name is owned by "referrer", except "unknown" which has no record
+                    Ok(RegistryQueryMsg::GetRecordByNode { node }) => {
+                        let label = get_label_from_name(&String::from("unknown"));
+                        let unknown_node =
+                            keccak256(&[&hex::decode(BASE_NODE).unwrap(), &label[..]].concat());
+                        if node == unknown_node {
+                            return SystemResult::Ok(ContractResult::Err(String::from(
+                                "Record not found",
+                            )));
+                        }
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&RecordResponse {
+                                owner: Addr::unchecked("referrer"),
+                                resolver: Addr::unchecked("resolver_address"),
+                                ttl: 0,
+                            })
+                            .unwrap(),
+                        ))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
             QueryRequest::Wasm(WasmQuery::Smart {
                 contract_addr: _,
                 msg,
@@ -83,19 +146,45 @@ impl WasmMockQuerier {
                 )),
                 Ok(RegistrarQueryMsg::GetBaseNode {}) => SystemResult::Ok(ContractResult::Ok(
                     to_binary(&GetBaseNodeResponse {
-                        base_node: String::from(
-                            "749f2b479b45e5da8e4cbecd926ee9a6f78db5424fa6993b6ecababa5d736b12",
-                        ),
+                        base_node: String::from(BASE_NODE),
                     })
                     .unwrap(),
                 )),
                 Ok(RegistrarQueryMsg::IsAvailable { .. }) => SystemResult::Ok(ContractResult::Ok(
                     to_binary(&IsAvailableResponse { available: true }).unwrap(),
                 )),
+                // Every name is released exactly at mock_env time
+                Ok(RegistrarQueryMsg::GetExpires { .. }) => SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&GetExpiresResponse {
+                        expires: mock_env().block.time.seconds() - 2_592_000,
+                    })
+                    .unwrap(),
+                )),
+                Ok(RegistrarQueryMsg::GetGracePeriod {}) => SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&GetGracePeriodResponse {
+                        grace_period: 2_592_000,
+                    })
+                    .unwrap(),
+                )),
                 _ => {
                     panic!("DO NOT ENTER HERE")
                 }
             },
+            QueryRequest::Custom(SeiQueryWrapper {
+                route: _,
+                query_data: SeiQuery::ExchangeRates {},
+            }) => SystemResult::Ok(ContractResult::Ok(
+                to_binary(&ExchangeRatesResponse {
+                    denom_oracle_exchange_rate_pairs: vec![DenomOracleExchangeRatePair {
+                        denom: String::from("usei"),
+                        oracle_exchange_rate: OracleExchangeRate {
+                            exchange_rate: Decimal::one(),
+                            last_update: Uint64::new(mock_env().block.height),
+                        },
+                    }],
+                })
+                .unwrap(),
+            )),
             _ => self.base.handle_query(request),
         }
     }
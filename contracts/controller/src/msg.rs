@@ -1,39 +1,69 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+use dotlabs::name_policy::{NamePolicy, NameRule};
+
+use crate::state::{
+    AcceptedDenom, Auction, AutoRenew, AutoRenewConfig, BlocklistEntry, DurationDiscount,
+    ExpiryPremium, FeeSplit, MerkleCampaign, PremiumName, PriceSource, PriceTier, PromoCode,
+    ReferralStats, ReferralTier,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub registrar_address: String,
     pub reverse_registrar_address: String,
+    pub max_commitment_age: u64,
+    pub min_commitment_age: u64,
     pub min_registration_duration: u64,
-    pub tier1_price: u64,
-    pub tier2_price: u64,
-    pub tier3_price: u64,
+    pub max_registration_duration: Option<u64>,
+    /// Caps how far in the future registrations and renewals may push the expiry
+    pub max_expiry_duration: Option<u64>,
+    pub duration_discounts: Vec<DurationDiscount>,
+    /// Defaults to `NamePolicy::default()`
+    pub name_policy: Option<NamePolicy>,
+    pub price_tiers: Vec<PriceTier>,
     pub whitelist_price: u64,
+    pub price_source: PriceSource,
+    pub accepted_denoms: Vec<AcceptedDenom>,
+    pub expiry_premium: Option<ExpiryPremium>,
+    pub fee_split: Option<FeeSplit>,
     pub referal_percentage: (u32, u32),
     pub enable_registration: bool,
     pub description: String,
+    /// Receives withdrawn funds, defaults to the instantiator
+    pub treasury: Option<String>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
+    Commit {
+        commitment: String,
+    },
     Register {
         name: String,
         owner: String,
         duration: u64,
+        secret: String,
         resolver: Option<String>,
         address: Option<String>,
         reverse_record: bool,
+        promo_code: Option<String>,
     },
     ReferalRegister {
         name: String,
         owner: String,
         duration: u64,
+        secret: String,
         resolver: Option<String>,
         address: Option<String>,
         referer: Option<String>,
         reverse_record: bool,
     },
+    PruneCommitments {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     OwnerRegister {
         name: String,
         owner: String,
@@ -43,20 +73,26 @@ pub enum ExecuteMsg {
         reverse_record: bool,
     },
     SetConfig {
+        max_commitment_age: u64,
+        min_commitment_age: u64,
         min_registration_duration: u64,
-        tier1_price: u64,
-        tier2_price: u64,
-        tier3_price: u64,
         registrar_address: String,
         reverse_registrar_address: String,
         owner: String,
         enable_registration: bool,
         description: String,
     },
-    Withdraw {},
+    /// Sends `amount` of `denom` (a native denom or CW20 token address) to `recipient`,
+    /// the treasury by default. Without a denom every accepted denom and token is swept.
+    Withdraw {
+        denom: Option<String>,
+        amount: Option<Uint128>,
+        recipient: Option<String>,
+    },
     Renew {
         name: String,
         duration: u64,
+        promo_code: Option<String>,
     },
     OwnerRenew {
         name: String,
@@ -71,6 +107,11 @@ pub enum ExecuteMsg {
     AddWhiteListByOwner {
         ensname: String,
         referal_percentage: Option<u32>,
+        /// Unix timestamp in seconds after which the entry no longer applies
+        expires: Option<u64>,
+    },
+    RemoveWhiteList {
+        ensname: String,
     },
     SetReferalPercentage {
         normal_percentage: u32,
@@ -79,6 +120,190 @@ pub enum ExecuteMsg {
     SetWhitelistPrice {
         price: u64,
     },
+    SetPriceSource {
+        price_source: PriceSource,
+    },
+    AddPriceTier {
+        min_length: u32,
+        max_length: Option<u32>,
+        annual_price: u64,
+    },
+    UpdatePriceTier {
+        min_length: u32,
+        max_length: Option<u32>,
+        annual_price: u64,
+    },
+    RemovePriceTier {
+        min_length: u32,
+    },
+    SetPremiumName {
+        name: String,
+        premium: PremiumName,
+    },
+    SetExpiryPremium {
+        expiry_premium: Option<ExpiryPremium>,
+    },
+    StartAuction {
+        name: String,
+        reserve_price: Uint128,
+        min_increment: Uint128,
+        bidding_period: u64,
+        extension: u64,
+        duration: u64,
+    },
+    CancelAuction {
+        name: String,
+    },
+    Bid {
+        name: String,
+    },
+    SettleAuction {
+        name: String,
+    },
+    RemovePremiumName {
+        name: String,
+    },
+    Receive(Cw20ReceiveMsg),
+    SetCw20Token {
+        token: String,
+        rate: Decimal,
+    },
+    RemoveCw20Token {
+        token: String,
+    },
+    SetAcceptedDenom {
+        denom: String,
+        price_source: PriceSource,
+    },
+    RemoveAcceptedDenom {
+        denom: String,
+    },
+    SetTreasury {
+        treasury: String,
+    },
+    SetFeeSplit {
+        fee_split: Option<FeeSplit>,
+    },
+    /// Splits the accumulated revenue according to the fee split. Callable by anyone.
+    Distribute {},
+    /// Pays out the referral rewards credited to the sender for every referrer name.
+    ClaimReferralRewards {},
+    /// Creates or replaces the promo code whose keccak256 hash is `code_hash` (hex encoded).
+    SetPromoCode {
+        code_hash: String,
+        promo_code: PromoCode,
+    },
+    RemovePromoCode {
+        code_hash: String,
+    },
+    /// Creates or replaces a Merkle allowlist campaign.
+    SetMerkleCampaign {
+        campaign: String,
+        merkle_campaign: MerkleCampaign,
+    },
+    RemoveMerkleCampaign {
+        campaign: String,
+    },
+    /// Registers `name` for the campaign duration if the sender is in the campaign's Merkle tree.
+    /// `proof` holds the hex encoded sibling hashes from the leaf up to the root.
+    ClaimWithProof {
+        campaign: String,
+        name: String,
+        owner: String,
+        secret: String,
+        resolver: Option<String>,
+        address: Option<String>,
+        reverse_record: bool,
+        proof: Vec<String>,
+    },
+    /// Registers every name with one combined payment. Unavailable names fail the whole batch
+    /// unless `skip_unavailable` is set, in which case they are skipped and not charged.
+    BatchRegister {
+        registrations: Vec<RegisterRequest>,
+        skip_unavailable: bool,
+    },
+    /// Renews every name with one combined payment.
+    BatchRenew {
+        renewals: Vec<RenewRequest>,
+    },
+    /// Escrows the sent funds to auto-renew `name`. Only the owner of the name can deposit, the
+    /// renewal `duration` defaults to the minimum registration duration.
+    DepositAutoRenew {
+        name: String,
+        duration: Option<u64>,
+    },
+    /// Returns `amount` of the escrow of `name` to the address that deposited it, everything by
+    /// default.
+    WithdrawAutoRenew {
+        name: String,
+        amount: Option<Uint128>,
+    },
+    SetAutoRenewConfig {
+        auto_renew_config: AutoRenewConfig,
+    },
+    /// Renews up to `limit` names close to expiry from their escrow and tips the sender.
+    /// Callable by anyone, each call resumes where the previous one stopped. Names that can't be
+    /// renewed are reported in `skipped` attributes, reserved or capped ones are refunded.
+    ProcessRenewals {
+        limit: Option<u32>,
+    },
+    SetDurationDiscounts {
+        duration_discounts: Vec<DurationDiscount>,
+    },
+    SetDurationLimits {
+        max_registration_duration: Option<u64>,
+        max_expiry_duration: Option<u64>,
+    },
+    SetNamePolicy {
+        name_policy: NamePolicy,
+    },
+    /// Blocked names can only be registered with `OwnerRegister`.
+    AddToBlocklist {
+        entries: Vec<BlocklistEntry>,
+    },
+    RemoveFromBlocklist {
+        entries: Vec<BlocklistEntry>,
+    },
+    /// Replaces the referral tier table. Without tiers `referal_percentage.0` applies.
+    SetReferralTiers {
+        tiers: Vec<ReferralTier>,
+    },
+}
+
+/// Registration of a single name in a `BatchRegister`, committed to like a `Register`.
+#[cw_serde]
+pub struct RegisterRequest {
+    pub name: String,
+    pub owner: String,
+    pub duration: u64,
+    pub secret: String,
+    pub resolver: Option<String>,
+    pub address: Option<String>,
+    pub reverse_record: bool,
+}
+
+#[cw_serde]
+pub struct RenewRequest {
+    pub name: String,
+    pub duration: u64,
+}
+
+/// Messages embedded in a CW20 `Send` to pay with an accepted token.
+#[cw_serde]
+pub enum ReceiveMsg {
+    Register {
+        name: String,
+        owner: String,
+        duration: u64,
+        secret: String,
+        resolver: Option<String>,
+        address: Option<String>,
+        reverse_record: bool,
+    },
+    Renew {
+        name: String,
+        duration: u64,
+    },
 }
 
 #[cw_serde]
@@ -89,12 +314,31 @@ pub enum QueryMsg {
     #[returns(Binary)]
     Registrar {},
     #[returns(Binary)]
-    RentPrice { name: String, duration: u64 },
+    RentPrice {
+        name: String,
+        duration: u64,
+        promo_code: Option<String>,
+    },
+    #[returns(Binary)]
+    MaxCommitmentAge {},
+    #[returns(Binary)]
+    MinCommitmentAge {},
     #[returns(Binary)]
     MinRegistrationDuration {},
     #[returns(Binary)]
+    DurationLimits {},
+    #[returns(Binary)]
+    DurationDiscounts {},
+    #[returns(Binary)]
     IsValidName { name: String },
     #[returns(Binary)]
+    NamePolicy {},
+    #[returns(Binary)]
+    Blocklist {
+        start_after: Option<BlocklistEntry>,
+        limit: Option<u32>,
+    },
+    #[returns(Binary)]
     GetTokenId { name: String },
     #[returns(Binary)]
     GetNodehash { name: String },
@@ -102,13 +346,104 @@ pub enum QueryMsg {
     GetNodeInfo { name: String },
     #[returns(Binary)]
     GetPrice {},
+    #[returns(Binary)]
+    GetPriceSource {},
+    #[returns(Binary)]
+    PriceTiers {},
+    #[returns(Binary)]
+    GetExpiryPremium {},
+    #[returns(Binary)]
+    AcceptedDenoms {},
+    #[returns(Binary)]
+    Treasury {},
+    #[returns(Binary)]
+    GetFeeSplit {},
+    /// Lifetime totals and unclaimed balance of a referrer name
+    #[returns(Binary)]
+    ReferralStats { name: String },
+    /// Unclaimed rewards of an address, summed over the referrer names it owned when referred
+    #[returns(Binary)]
+    ReferralRewards { address: String },
+    #[returns(Binary)]
+    WhiteList {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Binary)]
+    PromoCode { code_hash: String },
+    #[returns(Binary)]
+    MerkleCampaign { campaign: String },
+    #[returns(Binary)]
+    IsClaimed { campaign: String, address: String },
+    #[returns(Binary)]
+    AutoRenew { name: String },
+    #[returns(Binary)]
+    GetAutoRenewConfig {},
+    #[returns(Binary)]
+    ReferralTiers {},
+    #[returns(Binary)]
+    ReferralTier { name: String },
+    #[returns(Binary)]
+    Cw20Tokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Binary)]
+    Auction { name: String },
+    #[returns(Binary)]
+    Auctions {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Binary)]
+    PremiumNames {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Binary)]
+    MakeCommitment {
+        name: String,
+        owner: String,
+        secret: String,
+        resolver: Option<String>,
+        address: Option<String>,
+    },
+    #[returns(Binary)]
+    CommitmentTimestamp { commitment: String },
 }
 
 // We define a custom struct for each query response
 
 #[cw_serde]
 pub struct RentPriceResponse {
+    /// Final amount to pay, in `denom`.
     pub price: Uint128,
+    pub denom: String,
+    pub duration: u64,
+    /// Annual price of the name in price units, from its premium price or length tier.
+    pub annual_price: u64,
+    /// Price units per unit of `denom`, if the price source converts them.
+    pub exchange_rate: Option<Decimal>,
+    /// Price of the name for `duration` before any discount or premium.
+    pub base_price: Uint128,
+    /// Amount taken off `base_price` by the multi-year discount.
+    pub duration_discount: Uint128,
+    /// Part of `price` charged because the name was recently released.
+    pub premium: Uint128,
+    /// Amount taken off `price` by the promo code.
+    pub discount: Uint128,
+    /// `price` converted to each accepted denom with an available rate.
+    pub quotes: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct MaxCommitmentAgeResponse {
+    pub age: u64,
+}
+
+#[cw_serde]
+pub struct MinCommitmentAgeResponse {
+    pub age: u64,
 }
 
 #[cw_serde]
@@ -116,9 +451,29 @@ pub struct MinRegistrationDurationResponse {
     pub duration: u64,
 }
 
+#[cw_serde]
+pub struct DurationLimitsResponse {
+    pub min_registration_duration: u64,
+    pub max_registration_duration: Option<u64>,
+    pub max_expiry_duration: Option<u64>,
+}
+
+#[cw_serde]
+pub struct DurationDiscountsResponse {
+    pub duration_discounts: Vec<DurationDiscount>,
+}
+
 #[cw_serde]
 pub struct IsValidNameResponse {
     pub is_valid_name: bool,
+    pub is_reserved: bool,
+    /// First rule the name fails
+    pub failed_rule: Option<NameRule>,
+}
+
+#[cw_serde]
+pub struct NamePolicyResponse {
+    pub name_policy: NamePolicy,
 }
 
 #[cw_serde]
@@ -138,6 +493,16 @@ pub struct NodeInfoResponse {
     pub node: Vec<u8>,
 }
 
+#[cw_serde]
+pub struct MakeCommitmentResponse {
+    pub commitment: String,
+}
+
+#[cw_serde]
+pub struct CommitmentTimestampResponse {
+    pub timestamp: u64,
+}
+
 #[cw_serde]
 pub struct OwnerResponse {
     pub owner: Addr,
@@ -150,11 +515,170 @@ pub struct RegistrarResponse {
 
 #[cw_serde]
 pub struct PriceResponse {
-    pub tier1_price: u64,
-    pub tier2_price: u64,
-    pub tier3_price: u64,
+    pub price_tiers: Vec<PriceTier>,
     pub whitelist_price: u64,
 }
 
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct PremiumNameInfo {
+    pub name: String,
+    pub premium: PremiumName,
+}
+
+#[cw_serde]
+pub struct PremiumNamesResponse {
+    pub names: Vec<PremiumNameInfo>,
+}
+
+/// Exact labels are listed as `LabelHash` entries.
+#[cw_serde]
+pub struct BlocklistResponse {
+    pub entries: Vec<BlocklistEntry>,
+}
+
+#[cw_serde]
+pub struct Cw20TokenInfo {
+    pub token: Addr,
+    pub rate: Decimal,
+}
+
+#[cw_serde]
+pub struct Cw20TokensResponse {
+    pub tokens: Vec<Cw20TokenInfo>,
+}
+
+#[cw_serde]
+pub struct AuctionResponse {
+    pub name: String,
+    pub auction: Auction,
+}
+
+#[cw_serde]
+pub struct AuctionsResponse {
+    pub auctions: Vec<AuctionResponse>,
+}
+
+#[cw_serde]
+pub struct ExpiryPremiumResponse {
+    pub expiry_premium: Option<ExpiryPremium>,
+}
+
+#[cw_serde]
+pub struct AcceptedDenomsResponse {
+    pub denoms: Vec<AcceptedDenom>,
+}
+
+#[cw_serde]
+pub struct ReferralStatsResponse {
+    pub name: String,
+    pub stats: ReferralStats,
+}
+
+#[cw_serde]
+pub struct PromoCodeResponse {
+    pub code_hash: String,
+    pub promo_code: PromoCode,
+    pub uses: u64,
+}
+
+#[cw_serde]
+pub struct MerkleCampaignResponse {
+    pub campaign: String,
+    pub merkle_campaign: MerkleCampaign,
+}
+
+#[cw_serde]
+pub struct IsClaimedResponse {
+    pub claimed: bool,
+}
+
+#[cw_serde]
+pub struct AutoRenewResponse {
+    pub name: String,
+    pub auto_renew: Option<AutoRenew>,
+}
+
+#[cw_serde]
+pub struct AutoRenewConfigResponse {
+    pub auto_renew_config: AutoRenewConfig,
+}
+
+#[cw_serde]
+pub struct WhiteListInfo {
+    pub name: String,
+    pub node: Vec<u8>,
+    pub owner: Addr,
+    pub percentage: u32,
+    pub expires: Option<u64>,
+}
+
+#[cw_serde]
+pub struct WhiteListResponse {
+    pub entries: Vec<WhiteListInfo>,
+}
+
+#[cw_serde]
+pub struct ReferralTiersResponse {
+    pub tiers: Vec<ReferralTier>,
+}
+
+#[cw_serde]
+pub struct ReferralTierResponse {
+    pub name: String,
+    pub referrals: u64,
+    /// Tier reached by the name, if any
+    pub tier: Option<ReferralTier>,
+    /// Percentage the next referral with the name is rewarded with
+    pub percentage: u32,
+}
+
+#[cw_serde]
+pub struct ReferralRewardsResponse {
+    pub address: Addr,
+    pub unclaimed: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct FeeSplitResponse {
+    pub fee_split: Option<FeeSplit>,
+}
+
+#[cw_serde]
+pub struct TreasuryResponse {
+    pub treasury: Addr,
+}
+
+#[cw_serde]
+pub struct PriceTiersResponse {
+    pub tiers: Vec<PriceTier>,
+}
+
+#[cw_serde]
+pub struct PriceSourceResponse {
+    pub price_source: PriceSource,
+}
+
+/// Query interface a price feed contract has to implement to be used as `PriceSource::PriceFeed`.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum PriceFeedQueryMsg {
+    /// Price of one unit of `denom` in USD
+    #[returns(PriceFeedResponse)]
+    Price { denom: String },
+}
+
+#[cw_serde]
+pub struct PriceFeedResponse {
+    pub rate: Decimal,
+    /// Unix timestamp in seconds of the last update
+    pub last_updated: u64,
+}
+
+/// Settings missing from deployments with fixed tier prices. Ignored once the config has been
+/// migrated.
+#[cw_serde]
+pub struct MigrateMsg {
+    pub max_commitment_age: u64,
+    pub min_commitment_age: u64,
+    pub price_source: PriceSource,
+}
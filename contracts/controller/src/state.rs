@@ -1,9 +1,96 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::CanonicalAddr;
+use cosmwasm_std::{Addr, CanonicalAddr, Coin, Decimal, Uint128};
 use cw_storage_plus::{Item, Map};
+use dotlabs::name_policy::NamePolicy;
+
+#[cw_serde]
+pub enum PriceSource {
+    /// Tier prices are charged as is, in the payment denom.
+    Fixed {},
+    /// Tier prices are in USD and converted with the Sei oracle rate of the payment denom.
+    /// The oracle reports the block height of its last update, so staleness is counted in blocks.
+    SeiOracle { max_staleness_blocks: u64 },
+    /// Tier prices are in USD and converted with the rate returned by a price feed contract.
+    PriceFeed {
+        contract: String,
+        max_staleness_secs: u64,
+    },
+}
+
+/// Native denom accepted as payment besides `REGISTER_FEE_DENOM`, priced through its own source.
+#[cw_serde]
+pub struct AcceptedDenom {
+    pub denom: String,
+    pub price_source: PriceSource,
+}
+
+#[cw_serde]
+pub enum FeeRecipient {
+    Address { address: Addr },
+    /// The share is burned.
+    Burn {},
+}
+
+#[cw_serde]
+pub struct FeeShare {
+    pub recipient: FeeRecipient,
+    pub bps: u32,
+}
+
+/// Split of the registration revenue between recipients, the shares add up to `TOTAL_BPS`.
+/// With `distribute_on_register` each payment is split as it comes in, otherwise revenue
+/// accumulates until `Distribute {}` is called.
+#[cw_serde]
+pub struct FeeSplit {
+    pub shares: Vec<FeeShare>,
+    pub distribute_on_register: bool,
+}
+
+#[cw_serde]
+pub enum PremiumDecay {
+    /// The premium falls linearly to zero over the window.
+    Linear {},
+    /// The premium halves every `half_life` seconds and is dropped when the window ends.
+    Exponential { half_life: u64 },
+}
+
+/// One-off premium added to the cost of a name that was released after its grace period.
+/// `start_price` is in the same unit as the tier prices and `duration` is in seconds.
+#[cw_serde]
+pub struct ExpiryPremium {
+    pub start_price: u64,
+    pub duration: u64,
+    pub decay: PremiumDecay,
+}
 
 #[cw_serde]
 pub struct Config {
+    pub max_commitment_age: u64,
+    pub min_commitment_age: u64,
+    pub min_registration_duration: u64,
+    pub max_registration_duration: Option<u64>,
+    /// Longest time from now a registration or renewal may push the expiry to
+    pub max_expiry_duration: Option<u64>,
+    /// Sorted by `min_duration`
+    pub duration_discounts: Vec<DurationDiscount>,
+    pub name_policy: NamePolicy,
+    pub whitelist_price: u64,
+    pub price_source: PriceSource,
+    pub accepted_denoms: Vec<AcceptedDenom>,
+    pub expiry_premium: Option<ExpiryPremium>,
+    pub fee_split: Option<FeeSplit>,
+    pub referal_percentage: (u32, u32),
+    pub enable_registration: bool,
+    pub registrar_address: CanonicalAddr,
+    pub reverse_registrar_address: CanonicalAddr,
+    pub owner: CanonicalAddr,
+    pub treasury: CanonicalAddr,
+    pub description: String,
+}
+
+/// Config layout of deployments with fixed tier prices, read once by `migrate`.
+#[cw_serde]
+pub struct LegacyConfig {
     pub min_registration_duration: u64,
     pub tier1_price: u64,
     pub tier2_price: u64,
@@ -17,6 +104,181 @@ pub struct Config {
     pub description: String,
 }
 
+/// Annual price of names whose length is within `min_length..=max_length`.
+/// A tier without `max_length` covers every longer name.
+#[cw_serde]
+pub struct PriceTier {
+    pub min_length: u32,
+    pub max_length: Option<u32>,
+    pub annual_price: u64,
+}
+
+/// Percentage taken off the base price of registrations and renewals of at least `min_duration`.
+#[cw_serde]
+pub struct DurationDiscount {
+    pub min_duration: u64,
+    pub percentage: u32,
+}
+
+#[cw_serde]
+pub enum PremiumName {
+    /// Annual price charged instead of the length tier price.
+    Price { annual_price: u64 },
+    /// Only the owner can register or renew the name.
+    Reserved {},
+}
+
+#[cw_serde]
+pub enum BlockKind {
+    /// Exact label, stored as the hex encoded keccak256 hash of the label.
+    Label {},
+    /// Hex encoded keccak256 hash of an exact label, so the label itself stays off chain.
+    LabelHash {},
+    /// Blocks every label containing the entry.
+    Substring {},
+}
+
+#[cw_serde]
+pub struct BlocklistEntry {
+    pub entry: String,
+    pub kind: BlockKind,
+}
+
+#[cw_serde]
+pub struct Bid {
+    pub bidder: Addr,
+    pub amount: Uint128,
+}
+
+/// English auction of a name. Bids are escrowed in `REGISTER_FEE_DENOM` and a bid placed
+/// within `extension` seconds of `end_time` pushes `end_time` to `extension` seconds later.
+/// The winner is registered the name for `duration` seconds.
+#[cw_serde]
+pub struct Auction {
+    pub reserve_price: Uint128,
+    pub min_increment: Uint128,
+    pub end_time: u64,
+    pub extension: u64,
+    pub duration: u64,
+    pub highest_bid: Option<Bid>,
+}
+
+#[cw_serde]
+pub enum Discount {
+    /// Percentage taken off the cost, from 1 to 100.
+    Percentage { percentage: u32 },
+    /// Amount taken off the cost, in the same unit as the tier prices.
+    Fixed { amount: u64 },
+}
+
+/// Promotional code, stored under the hex encoded keccak256 hash of the code.
+/// Times are unix timestamps in seconds and lengths restrict the names the code applies to.
+#[cw_serde]
+pub struct PromoCode {
+    pub discount: Discount,
+    pub max_uses: Option<u64>,
+    pub max_uses_per_address: Option<u64>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub min_length: Option<u32>,
+    pub max_length: Option<u32>,
+}
+
+/// Allowlist of addresses that can each claim one name for `duration`. Leaves of the Merkle tree
+/// are `keccak256(address)` and pairs are hashed in sorted order. Claims are free without a discount,
+/// which only covers tier-priced names. Reserved names can never be claimed.
+#[cw_serde]
+pub struct MerkleCampaign {
+    /// Hex encoded Merkle root
+    pub merkle_root: String,
+    pub duration: u64,
+    pub discount: Option<Discount>,
+    /// Unix timestamp in seconds after which the campaign can no longer be claimed
+    pub end_time: Option<u64>,
+}
+
+/// Funds escrowed to renew a name for `duration` whenever it gets close to expiry. `owner` is the
+/// owner of the name that made the deposits, the balance is refunded to it once the name changes
+/// owner or is released.
+#[cw_serde]
+pub struct AutoRenew {
+    pub balance: Coin,
+    pub duration: u64,
+    pub owner: Addr,
+}
+
+/// Names are auto-renewed once they expire within `renew_window` seconds. The keeper processing a
+/// renewal is paid `keeper_tip_bps` of its cost on top, out of the escrow.
+#[cw_serde]
+pub struct AutoRenewConfig {
+    pub renew_window: u64,
+    pub keeper_tip_bps: u32,
+}
+
+/// Whitelisted referrer name, rewarded with `percentage` of each referral. The entry no longer
+/// applies once `expires` has passed or the name changed owner.
+#[cw_serde]
+pub struct WhitelistEntry {
+    pub node: Vec<u8>,
+    pub owner: Addr,
+    pub percentage: u32,
+    pub expires: Option<u64>,
+}
+
+/// Referral percentage applied once a referrer name has made `min_referrals` referrals.
+#[cw_serde]
+pub struct ReferralTier {
+    pub min_referrals: u64,
+    pub percentage: u32,
+}
+
+/// Referrals made with a referrer name. `rewards` is the total credited to the owners of the name,
+/// `unclaimed` the part of it they have not claimed yet.
+#[cw_serde]
+#[derive(Default)]
+pub struct ReferralStats {
+    pub referrals: u64,
+    pub volume: Vec<Coin>,
+    pub rewards: Vec<Coin>,
+    pub unclaimed: Vec<Coin>,
+}
+
 pub const REGISTER_FEE_DENOM: &str = "usei";
+pub const TOTAL_BPS: u32 = 10_000;
 pub const CONFIG: Item<Config> = Item::new("CONFIG");
-pub const WHITELIST: Map<String, (Vec<u8>, u32)> = Map::new("WHITELIST");
+pub const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("CONFIG");
+// Keyed by min_length, so tiers are iterated in ascending order
+pub const PRICE_TIERS: Map<u32, PriceTier> = Map::new("PRICE_TIERS");
+pub const PREMIUM_NAMES: Map<String, PremiumName> = Map::new("PREMIUM_NAMES");
+// Keyed by (kind, entry). Labels are only stored as hashes, under `BLOCKED_LABEL_HASH`.
+pub const BLOCKLIST: Map<(String, String), bool> = Map::new("BLOCKLIST");
+pub const BLOCKED_LABEL_HASH: &str = "label_hash";
+pub const BLOCKED_SUBSTRING: &str = "substring";
+// Accepted CW20 tokens and their rate in price units per token unit
+pub const CW20_TOKENS: Map<&Addr, Decimal> = Map::new("CW20_TOKENS");
+pub const AUCTIONS: Map<String, Auction> = Map::new("AUCTIONS");
+// Sum of the highest bids of open auctions, excluded from withdrawals
+pub const AUCTION_ESCROW: Item<Uint128> = Item::new("AUCTION_ESCROW");
+pub const COMMITMENTS: Map<String, u64> = Map::new("COMMITMENTS");
+pub const WHITELIST: Map<String, WhitelistEntry> = Map::new("WHITELIST");
+// Whitelist entries of the fixed-tier layout, (node, percentage)
+pub const LEGACY_WHITELIST: Map<String, (Vec<u8>, u32)> = Map::new("WHITELIST");
+pub const REFERRAL_STATS: Map<String, ReferralStats> = Map::new("REFERRAL_STATS");
+pub const PROMO_CODES: Map<String, PromoCode> = Map::new("PROMO_CODES");
+pub const PROMO_CODE_USES: Map<String, u64> = Map::new("PROMO_CODE_USES");
+pub const PROMO_CODE_ADDRESS_USES: Map<(String, &Addr), u64> = Map::new("PROMO_CODE_ADDRESS_USES");
+pub const MERKLE_CAMPAIGNS: Map<String, MerkleCampaign> = Map::new("MERKLE_CAMPAIGNS");
+// Addresses that claimed a name from a campaign
+pub const MERKLE_CLAIMS: Map<(String, &Addr), bool> = Map::new("MERKLE_CLAIMS");
+pub const AUTO_RENEW_CONFIG: Item<AutoRenewConfig> = Item::new("AUTO_RENEW_CONFIG");
+pub const AUTO_RENEWALS: Map<String, AutoRenew> = Map::new("AUTO_RENEWALS");
+// Auto-renew deposits per denom, excluded from withdrawals
+pub const AUTO_RENEW_ESCROW: Map<String, Uint128> = Map::new("AUTO_RENEW_ESCROW");
+// Last name processed by ProcessRenewals, the next call resumes after it
+pub const AUTO_RENEW_CURSOR: Item<String> = Item::new("AUTO_RENEW_CURSOR");
+// Keyed by min_referrals, so tiers are iterated in ascending order
+pub const REFERRAL_TIERS: Map<u64, ReferralTier> = Map::new("REFERRAL_TIERS");
+// Unclaimed rewards keyed by (owner of the referrer name at referral time, referrer name)
+pub const REFERRAL_REWARDS: Map<(&Addr, String), Vec<Coin>> = Map::new("REFERRAL_REWARDS");
+// Unclaimed rewards per denom, excluded from withdrawals
+pub const REFERRAL_ESCROW: Map<String, Uint128> = Map::new("REFERRAL_ESCROW");
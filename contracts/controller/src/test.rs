@@ -1,19 +1,60 @@
 mod tests {
-    use crate::contract::{execute, instantiate, query};
+    use crate::contract::{execute, instantiate, migrate, query};
     use crate::error::ContractError;
-    use crate::mock_querier::mock_dependencies;
+    use crate::handler::get_cost;
+    use crate::mock_querier::{mock_dependencies, BASE_NODE};
     use crate::msg::{
-        ExecuteMsg, InstantiateMsg, MinRegistrationDurationResponse, NodehashResponse,
-        OwnerResponse, PriceResponse, QueryMsg, RegistrarResponse, RentPriceResponse,
-        TokenIdResponse,
+        AcceptedDenomsResponse, AuctionResponse, AuctionsResponse, AutoRenewConfigResponse,
+        AutoRenewResponse, BlocklistResponse, CommitmentTimestampResponse, Cw20TokenInfo,
+        Cw20TokensResponse, DurationDiscountsResponse, DurationLimitsResponse, ExecuteMsg,
+        ExpiryPremiumResponse, FeeSplitResponse, InstantiateMsg, IsClaimedResponse,
+        IsValidNameResponse, MakeCommitmentResponse, MerkleCampaignResponse, MigrateMsg,
+        MinRegistrationDurationResponse, NamePolicyResponse, NodehashResponse, OwnerResponse,
+        PremiumNameInfo, PremiumNamesResponse, PriceResponse, PriceSourceResponse,
+        PriceTiersResponse, PromoCodeResponse, QueryMsg, ReceiveMsg, ReferralRewardsResponse,
+        ReferralStatsResponse, ReferralTierResponse, ReferralTiersResponse, RegisterRequest,
+        RegistrarResponse, RenewRequest, RentPriceResponse, TokenIdResponse, TreasuryResponse,
+        WhiteListInfo, WhiteListResponse,
+    };
+    use crate::state::{
+        AcceptedDenom, AutoRenew, AutoRenewConfig, Bid, BlockKind, BlocklistEntry, Discount,
+        DurationDiscount, ExpiryPremium, FeeRecipient, FeeShare, FeeSplit, LegacyConfig,
+        MerkleCampaign, PremiumDecay, PremiumName, PriceSource, PriceTier, PromoCode,
+        ReferralStats, ReferralTier, AUTO_RENEWALS, AUTO_RENEW_ESCROW, LEGACY_CONFIG,
+        LEGACY_WHITELIST,
     };
     use cosmwasm_std::testing::{mock_env, mock_info};
     use cosmwasm_std::{
-        coins, from_binary, to_binary, Addr, BankMsg, Coin, CosmosMsg, Uint128, WasmMsg,
+        coins, from_binary, to_binary, Addr, Api, Attribute, BankMsg, Coin, CosmosMsg, Decimal,
+        Deps, Env, OwnedDeps, StdError, Uint128, WasmMsg,
     };
+    use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+    use dotlabs::name_policy::{CharacterClass, NamePolicy, NameRule};
     use dotlabs::registrar::{ExecuteMsg as RegistrarExecuteMsg, Extension};
     use dotlabs::registry::ExecuteMsg as RegistryExecuteMsg;
     use dotlabs::resolver::ExecuteMsg as ResolverExecuteMsg;
+    use dotlabs::utils::{get_label_from_name, keccak256};
+    use sei_cosmwasm::SeiQueryWrapper;
+
+    fn price_tiers() -> Vec<PriceTier> {
+        vec![
+            PriceTier {
+                min_length: 3,
+                max_length: Some(3),
+                annual_price: 640_000_000u64,
+            },
+            PriceTier {
+                min_length: 4,
+                max_length: Some(4),
+                annual_price: 160_000_000u64,
+            },
+            PriceTier {
+                min_length: 5,
+                max_length: None,
+                annual_price: 5_000_000u64,
+            },
+        ]
+    }
 
     #[test]
     fn proper_initialization() {
@@ -21,14 +62,25 @@ mod tests {
         let msg = InstantiateMsg {
             registrar_address: String::from("registrar_address"),
             reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 0,
-            tier1_price: 640_000_000u64,
-            tier2_price: 160_000_000u64,
-            tier3_price: 5_000_000u64,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
             whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
             referal_percentage: (20, 40),
             enable_registration: true,
             description: "".to_string(),
+            treasury: None,
         };
         let info = mock_info("creator", &coins(0, "usei"));
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -41,14 +93,25 @@ mod tests {
         let msg = InstantiateMsg {
             registrar_address: String::from("registrar_address"),
             reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 0,
-            tier1_price: 640_000_000u64,
-            tier2_price: 160_000_000u64,
-            tier3_price: 5_000_000u64,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
             whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
             referal_percentage: (20, 40),
             enable_registration: true,
             description: "".to_string(),
+            treasury: None,
         };
         let info = mock_info("creator", &coins(0, "usei"));
         instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -69,14 +132,25 @@ mod tests {
         let msg = InstantiateMsg {
             registrar_address: String::from("registrar_address"),
             reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 0,
-            tier1_price: 640_000_000u64,
-            tier2_price: 160_000_000u64,
-            tier3_price: 5_000_000u64,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
             whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
             referal_percentage: (20, 40),
             enable_registration: true,
             description: "".to_string(),
+            treasury: None,
         };
         let info = mock_info("creator", &coins(0, "usei"));
         instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -101,15 +175,26 @@ mod tests {
         let msg = InstantiateMsg {
             registrar_address: registrar_address.clone(),
             reverse_registrar_address: reverse_registrar_address.clone(),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
 
-            tier1_price: 640_000_000u64,
-            tier2_price: 160_000_000u64,
-            tier3_price: 5_000_000u64,
+            price_tiers: price_tiers(),
             whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
             referal_percentage: (20, 40),
             enable_registration: true,
             description: "".to_string(),
+            treasury: None,
         };
         let info = mock_info("creator", &coins(0, "usei"));
         instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -118,6 +203,7 @@ mod tests {
         let owner = String::from("alice");
         let resolver = String::from("registry_address");
         let address = String::from("alice_addr");
+        let secret = String::from("secret");
 
         let duration: u64 = 24 * 3600 * 365;
         let res = query(
@@ -125,22 +211,47 @@ mod tests {
             mock_env(),
             QueryMsg::RentPrice {
                 name: name.clone(),
-                duration: duration.clone(),
+                duration,
+                promo_code: None,
             },
         )
         .unwrap();
         let rent_price_response: RentPriceResponse = from_binary(&res).unwrap();
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MakeCommitment {
+                name: name.clone(),
+                owner: owner.clone(),
+                secret: secret.clone(),
+                resolver: Some(resolver.clone()),
+                address: Some(address.clone()),
+            },
+        )
+        .unwrap();
+        let commitment_response: MakeCommitmentResponse = from_binary(&res).unwrap();
+        let msg = ExecuteMsg::Commit {
+            commitment: commitment_response.commitment,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+
+        // Wait until the commitment matures
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+
         let info = mock_info("alice", &coins(rent_price_response.price.u128(), "usei"));
         let msg = ExecuteMsg::Register {
             name: name.clone(),
             owner: owner.clone(),
-            duration: duration.clone(),
+            duration,
+            secret: secret.clone(),
 
             resolver: Some(resolver.clone()),
             address: Some(address.clone()),
             reverse_record: false,
+            promo_code: None,
         };
-        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
         let register_registrar_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: registrar_address.clone(),
@@ -149,10 +260,10 @@ mod tests {
                     "9c0257114eb9399a2985f8e75dad7600c5d89fe3824ffa99ec1c3eb8bf3b0501",
                 ), // token_id of alice
                 owner: mock_env().contract.address.to_string(),
-                duration: duration.clone(),
+                duration,
                 name: name.clone(),
                 extension: Extension {
-                    name: name,
+                    name,
                     description: String::from(""),
                 },
             })
@@ -221,6 +332,7 @@ mod tests {
         let owner = String::from("alice");
         let resolver = String::from("registry_address");
         let address = String::from("alice_addr");
+        let secret = String::from("secret");
 
         let duration: u64 = 24 * 3600 * 365;
         let res = query(
@@ -228,7 +340,8 @@ mod tests {
             mock_env(),
             QueryMsg::RentPrice {
                 name: name.clone(),
-                duration: duration.clone(),
+                duration,
+                promo_code: None,
             },
         )
         .unwrap();
@@ -237,12 +350,14 @@ mod tests {
         let msg = ExecuteMsg::Register {
             name: name.clone(),
             owner: owner.clone(),
-            duration: duration.clone(),
+            duration,
+            secret: secret.clone(),
             resolver: Some(resolver.clone()),
             address: Some(address.clone()),
             reverse_record: false,
+            promo_code: None,
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_err(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_err());
     }
 
     #[test] // Should return correct messages
@@ -253,15 +368,26 @@ mod tests {
         let msg = InstantiateMsg {
             registrar_address: registrar_address.clone(),
             reverse_registrar_address: reverse_registrar_address.clone(),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
 
-            tier1_price: 640_000_000u64,
-            tier2_price: 160_000_000u64,
-            tier3_price: 5_000_000u64,
+            price_tiers: price_tiers(),
             whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
             referal_percentage: (20, 40),
             enable_registration: true,
             description: "".to_string(),
+            treasury: None,
         };
         let info = mock_info("creator", &coins(0, "usei"));
         instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
@@ -274,7 +400,7 @@ mod tests {
         let msg = ExecuteMsg::OwnerRegister {
             name: name.clone(),
             owner: owner.clone(),
-            duration: duration.clone(),
+            duration,
             resolver: Some(resolver.clone()),
             address: Some(address.clone()),
             reverse_record: false,
@@ -287,10 +413,10 @@ mod tests {
                     "9c0257114eb9399a2985f8e75dad7600c5d89fe3824ffa99ec1c3eb8bf3b0501",
                 ), // token_id of alice
                 owner: mock_env().contract.address.to_string(),
-                duration: duration.clone(),
+                duration,
                 name: name.clone(),
                 extension: Extension {
-                    name: name,
+                    name,
                     description: String::from(""),
                 },
             })
@@ -363,12 +489,12 @@ mod tests {
         let msg = ExecuteMsg::OwnerRegister {
             name: name.clone(),
             owner: owner.clone(),
-            duration: duration.clone(),
+            duration,
             resolver: Some(resolver.clone()),
             address: Some(address.clone()),
             reverse_record: false,
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
     }
 
     #[test]
@@ -379,15 +505,26 @@ mod tests {
         let msg = InstantiateMsg {
             registrar_address: registrar_address.clone(),
             reverse_registrar_address: reverse_registrar_address.clone(),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
 
-            tier1_price: 640_000_000u64,
-            tier2_price: 160_000_000u64,
-            tier3_price: 5_000_000u64,
+            price_tiers: price_tiers(),
             whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
             referal_percentage: (20, 40),
             enable_registration: false,
             description: "".to_string(),
+            treasury: None,
         };
         let info = mock_info("creator", &coins(0, "usei"));
         instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -397,6 +534,7 @@ mod tests {
 
         let resolver = String::from("registry_address");
         let address = String::from("alice_addr");
+        let secret = String::from("secret");
 
         let duration: u64 = 24 * 3600 * 365;
         let res = query(
@@ -404,7 +542,8 @@ mod tests {
             mock_env(),
             QueryMsg::RentPrice {
                 name: name.clone(),
-                duration: duration.clone(),
+                duration,
+                promo_code: None,
             },
         )
         .unwrap();
@@ -413,14 +552,16 @@ mod tests {
         let msg = ExecuteMsg::Register {
             name: name.clone(),
             owner: owner.clone(),
-            duration: duration.clone(),
+            duration,
+            secret: secret.clone(),
 
             resolver: Some(resolver.clone()),
             address: Some(address.clone()),
 
             reverse_record: false,
+            promo_code: None,
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_err(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_err());
 
         let msg = ExecuteMsg::SetEnableRegistration {
             enable_registration: true,
@@ -433,18 +574,42 @@ mod tests {
         )
         .unwrap();
 
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MakeCommitment {
+                name: name.clone(),
+                owner: owner.clone(),
+                secret: secret.clone(),
+                resolver: Some(resolver.clone()),
+                address: Some(address.clone()),
+            },
+        )
+        .unwrap();
+        let commitment_response: MakeCommitmentResponse = from_binary(&res).unwrap();
+        let msg = ExecuteMsg::Commit {
+            commitment: commitment_response.commitment,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+
+        // Wait until the commitment matures
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+
         let info = mock_info("alice", &coins(rent_price_response.price.u128(), "usei"));
         let msg = ExecuteMsg::Register {
             name: name.clone(),
             owner: owner.clone(),
-            duration: duration.clone(),
+            duration,
+            secret: secret.clone(),
 
             resolver: Some(resolver.clone()),
             address: Some(address.clone()),
 
             reverse_record: false,
+            promo_code: None,
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), env, info, msg).is_ok());
     }
 
     #[test] // Should not be able to register with insufficient fund
@@ -455,15 +620,26 @@ mod tests {
         let msg = InstantiateMsg {
             registrar_address: registrar_address.clone(),
             reverse_registrar_address: reverse_registrar_address.clone(),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
 
-            tier1_price: 640_000_000u64,
-            tier2_price: 160_000_000u64,
-            tier3_price: 5_000_000u64,
+            price_tiers: price_tiers(),
             whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
             referal_percentage: (20, 40),
             enable_registration: true,
             description: "".to_string(),
+            treasury: None,
         };
         let info = mock_info("creator", &coins(0, "usei"));
         instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -472,6 +648,7 @@ mod tests {
         let owner = String::from("alice");
         let resolver = String::from("registry_address");
         let address = String::from("alice_addr");
+        let secret = String::from("secret");
 
         let duration: u64 = 24 * 3600 * 365;
         let res = query(
@@ -479,7 +656,8 @@ mod tests {
             mock_env(),
             QueryMsg::RentPrice {
                 name: name.clone(),
-                duration: duration.clone(),
+                duration,
+                promo_code: None,
             },
         )
         .unwrap();
@@ -493,12 +671,14 @@ mod tests {
         let msg = ExecuteMsg::Register {
             name: name.clone(),
             owner: owner.clone(),
-            duration: duration.clone(),
+            duration,
+            secret: secret.clone(),
 
             resolver: Some(resolver.clone()),
             address: Some(address.clone()),
 
             reverse_record: false,
+            promo_code: None,
         };
 
         let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
@@ -519,15 +699,26 @@ mod tests {
         let msg = InstantiateMsg {
             registrar_address: registrar_address.clone(),
             reverse_registrar_address: reverse_registrar_address.clone(),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
 
-            tier1_price: 640_000_000u64,
-            tier2_price: 160_000_000u64,
-            tier3_price: 5_000_000u64,
+            price_tiers: price_tiers(),
             whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
             referal_percentage: (20, 40),
             enable_registration: true,
             description: "".to_string(),
+            treasury: None,
         };
         let info = mock_info("creator", &coins(0, "usei"));
         instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -536,6 +727,7 @@ mod tests {
         let owner = String::from("alice");
         let resolver = String::from("registry_address");
         let address = String::from("alice_addr");
+        let secret = String::from("secret");
 
         let duration: u64 = 24 * 3600 * 365;
         let res = query(
@@ -543,30 +735,56 @@ mod tests {
             mock_env(),
             QueryMsg::RentPrice {
                 name: name.clone(),
-                duration: duration.clone(),
+                duration,
+                promo_code: None,
             },
         )
         .unwrap();
         let rent_price_response: RentPriceResponse = from_binary(&res).unwrap();
 
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MakeCommitment {
+                name: name.clone(),
+                owner: owner.clone(),
+                secret: secret.clone(),
+                resolver: Some(resolver.clone()),
+                address: Some(address.clone()),
+            },
+        )
+        .unwrap();
+        let commitment_response: MakeCommitmentResponse = from_binary(&res).unwrap();
+        let msg = ExecuteMsg::Commit {
+            commitment: commitment_response.commitment,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+
+        // Wait until the commitment matures
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+
         let info = mock_info("alice", &coins(rent_price_response.price.u128(), "usei"));
         let msg = ExecuteMsg::Register {
             name: name.clone(),
             owner: owner.clone(),
-            duration: duration.clone(),
+            duration,
+            secret: secret.clone(),
 
             resolver: Some(resolver.clone()),
             address: Some(address.clone()),
 
             reverse_record: false,
+            promo_code: None,
         };
 
-        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
         let info = mock_info("alice", &coins(rent_price_response.price.u128(), "usei"));
         let msg = ExecuteMsg::Renew {
             name: name.clone(),
-            duration: duration.clone(),
+            duration,
+            promo_code: None,
         };
 
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -577,7 +795,7 @@ mod tests {
                 id: String::from(
                     "9c0257114eb9399a2985f8e75dad7600c5d89fe3824ffa99ec1c3eb8bf3b0501",
                 ), // token_id of alice,,
-                duration: duration.clone(),
+                duration,
             })
             .unwrap(),
             funds: vec![],
@@ -595,15 +813,26 @@ mod tests {
         let msg = InstantiateMsg {
             registrar_address: registrar_address.clone(),
             reverse_registrar_address: reverse_registrar_address.clone(),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
 
-            tier1_price: 640_000_000u64,
-            tier2_price: 160_000_000u64,
-            tier3_price: 5_000_000u64,
+            price_tiers: price_tiers(),
             whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
             referal_percentage: (20, 40),
             enable_registration: true,
             description: "".to_string(),
+            treasury: None,
         };
         let info = mock_info("creator", &coins(0, "usei"));
         instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -615,7 +844,8 @@ mod tests {
             mock_env(),
             QueryMsg::RentPrice {
                 name: name.clone(),
-                duration: duration.clone(),
+                duration,
+                promo_code: None,
             },
         )
         .unwrap();
@@ -626,7 +856,8 @@ mod tests {
         let info = mock_info("alice", &coins(half, "usei"));
         let msg = ExecuteMsg::Renew {
             name: name.clone(),
-            duration: duration.clone(),
+            duration,
+            promo_code: None,
         };
 
         let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
@@ -647,35 +878,41 @@ mod tests {
         let msg = InstantiateMsg {
             registrar_address: registrar_address.clone(),
             reverse_registrar_address: reverse_registrar_address.clone(),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
 
-            tier1_price: 640_000_000u64,
-            tier2_price: 160_000_000u64,
-            tier3_price: 5_000_000u64,
+            price_tiers: price_tiers(),
             whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
             referal_percentage: (20, 40),
             enable_registration: true,
             description: "".to_string(),
+            treasury: None,
         };
         let info = mock_info("creator", &coins(0, "usei"));
         instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
         let info = mock_info("creator", &coins(0, "usei"));
-        let msg = ExecuteMsg::Withdraw {};
-
-        // Zero balance
-        let bank_send_message: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
-            to_address: "creator".to_string(),
-            amount: vec![Coin {
-                denom: "usei".to_string(),
-                amount: Uint128::from(0 as u32),
-            }],
-        });
+        let msg = ExecuteMsg::Withdraw {
+            denom: None,
+            amount: None,
+            recipient: None,
+        };
 
+        // Zero balances are skipped
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-        assert_eq!(res.messages.len(), 1); // Renew
-        assert_eq!(res.messages[0].msg, bank_send_message);
+        assert_eq!(res.messages.len(), 0);
     }
 
     #[test] // Should return error if withdraw with non-owner
@@ -686,21 +923,36 @@ mod tests {
         let msg = InstantiateMsg {
             registrar_address: registrar_address.clone(),
             reverse_registrar_address: reverse_registrar_address.clone(),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
 
-            tier1_price: 640_000_000u64,
-            tier2_price: 160_000_000u64,
-            tier3_price: 5_000_000u64,
+            price_tiers: price_tiers(),
             whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
             referal_percentage: (20, 40),
             enable_registration: true,
             description: "".to_string(),
+            treasury: None,
         };
         let info = mock_info("creator", &coins(0, "usei"));
         instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
         let info = mock_info("alice", &coins(0, "usei"));
-        let msg = ExecuteMsg::Withdraw {};
+        let msg = ExecuteMsg::Withdraw {
+            denom: None,
+            amount: None,
+            recipient: None,
+        };
 
         let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
         assert_eq!(
@@ -720,24 +972,34 @@ mod tests {
         let msg = InstantiateMsg {
             registrar_address: registrar_address.clone(),
             reverse_registrar_address: reverse_registrar_address.clone(),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
 
-            tier1_price: 640_000_000u64,
-            tier2_price: 160_000_000u64,
-            tier3_price: 5_000_000u64,
+            price_tiers: price_tiers(),
             whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
             referal_percentage: (20, 40),
             enable_registration: true,
             description: "".to_string(),
+            treasury: None,
         };
         let info = mock_info("creator", &coins(0, "usei"));
         instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
         let msg = ExecuteMsg::SetConfig {
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 24 * 3600 * 365 * 2,
-            tier1_price: 6_000_000u64,
-            tier2_price: 5_000_000u64,
-            tier3_price: 4_000_000u64,
             enable_registration: true,
             description: "".to_string(),
             registrar_address: String::from("new_registrar_address"),
@@ -782,9 +1044,7 @@ mod tests {
         assert_eq!(
             res,
             PriceResponse {
-                tier1_price: 6_000_000u64,
-                tier2_price: 5_000_000u64,
-                tier3_price: 4_000_000u64,
+                price_tiers: price_tiers(),
                 whitelist_price: 640_000_000u64,
             }
         );
@@ -798,24 +1058,34 @@ mod tests {
         let msg = InstantiateMsg {
             registrar_address: registrar_address.clone(),
             reverse_registrar_address: reverse_registrar_address.clone(),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
 
-            tier1_price: 640_000_000u64,
-            tier2_price: 160_000_000u64,
-            tier3_price: 5_000_000u64,
+            price_tiers: price_tiers(),
             whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
             referal_percentage: (20, 40),
             enable_registration: true,
             description: "".to_string(),
+            treasury: None,
         };
         let info = mock_info("creator", &coins(0, "usei"));
         instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
         let msg = ExecuteMsg::SetConfig {
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 24 * 3600 * 365 * 2,
-            tier1_price: 6_000_000u64,
-            tier2_price: 5_000_000u64,
-            tier3_price: 4_000_000u64,
             enable_registration: true,
             description: "".to_string(),
             registrar_address: String::from("new_registrar_address"),
@@ -842,23 +1112,33 @@ mod tests {
         let msg = InstantiateMsg {
             registrar_address: registrar_address.clone(),
             reverse_registrar_address: reverse_registrar_address.clone(),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
 
-            tier1_price: 640_000_000u64,
-            tier2_price: 160_000_000u64,
-            tier3_price: 5_000_000u64,
+            price_tiers: price_tiers(),
             whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
             referal_percentage: (20, 40),
             enable_registration: true,
             description: "".to_string(),
+            treasury: None,
         };
         let info = mock_info("creator", &coins(0, "usei"));
         instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
         let msg = ExecuteMsg::SetConfig {
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 24 * 3600 * 365 * 2,
-            tier1_price: 6_000_000u64,
-            tier2_price: 5_000_000u64,
-            tier3_price: 4_000_000u64,
             enable_registration: true,
             description: "".to_string(),
             registrar_address: String::from("new_registrar_address"),
@@ -878,10 +1158,9 @@ mod tests {
         );
 
         let msg = ExecuteMsg::SetConfig {
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
             min_registration_duration: 24 * 3600 * 365 * 2,
-            tier1_price: 6_000_000u64,
-            tier2_price: 5_000_000u64,
-            tier3_price: 4_000_000u64,
             enable_registration: true,
             description: "".to_string(),
             registrar_address: String::from("new_registrar_address"),
@@ -901,4 +1180,3321 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_commit_and_register() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let name = String::from("alice");
+        let owner = String::from("alice");
+        let resolver = String::from("registry_address");
+        let address = String::from("alice_addr");
+        let secret = String::from("secret");
+        let duration: u64 = 24 * 3600 * 365;
+
+        let register_msg = ExecuteMsg::Register {
+            name: name.clone(),
+            owner: owner.clone(),
+            duration,
+            secret: secret.clone(),
+            resolver: Some(resolver.clone()),
+            address: Some(address.clone()),
+            reverse_record: false,
+            promo_code: None,
+        };
+        let info = mock_info("alice", &coins(5_000_000u128, "usei"));
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MakeCommitment {
+                name: name.clone(),
+                owner: owner.clone(),
+                secret: secret.clone(),
+                resolver: Some(resolver.clone()),
+                address: Some(address.clone()),
+            },
+        )
+        .unwrap();
+        let commitment = from_binary::<MakeCommitmentResponse>(&res)
+            .unwrap()
+            .commitment;
+
+        // Register without commitment
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), register_msg.clone())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::ConsumeNonexistCommitment {
+                commitment: commitment.clone()
+            }
+        );
+
+        let msg = ExecuteMsg::Commit {
+            commitment: commitment.clone(),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg.clone()).unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::CommitmentTimestamp {
+                commitment: commitment.clone(),
+            },
+        )
+        .unwrap();
+        let res: CommitmentTimestampResponse = from_binary(&res).unwrap();
+        let commit_time = mock_env().block.time.seconds();
+        assert_eq!(res.timestamp, commit_time);
+
+        // Recommit before the previous commitment expires
+        let err = execute(deps.as_mut(), mock_env(), mock_info("bob", &[]), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::RecommitTooEarly {
+                commit_expired: commit_time + 86400,
+                current: commit_time,
+            }
+        );
+
+        // Reveal before the commitment matures
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), register_msg.clone())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::CommitmentIsTooEarlyOrExpired {
+                commit_expired: commit_time + 86400,
+                commit_matured: commit_time + 60,
+                current: commit_time,
+            }
+        );
+
+        // Reveal with another secret
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+        let msg = ExecuteMsg::Register {
+            name: name.clone(),
+            owner: owner.clone(),
+            duration,
+            secret: String::from("other_secret"),
+            resolver: Some(resolver.clone()),
+            address: Some(address.clone()),
+            reverse_record: false,
+            promo_code: None,
+        };
+        assert!(execute(deps.as_mut(), env.clone(), info.clone(), msg).is_err());
+
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), register_msg.clone()).unwrap();
+        assert_eq!(res.messages.len(), 5);
+
+        // Commitment is consumed
+        let err = execute(deps.as_mut(), env, info, register_msg).unwrap_err();
+        assert_eq!(err, ContractError::ConsumeNonexistCommitment { commitment });
+    }
+
+    #[test]
+    fn test_commitment_expired() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::Commit {
+            commitment: String::from("commitment_a"),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(3600);
+        let msg = ExecuteMsg::Commit {
+            commitment: String::from("commitment_b"),
+        };
+        execute(deps.as_mut(), env, mock_info("bob", &[]), msg).unwrap();
+
+        // Only commitment_a is stale
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(86400 + 60);
+        let msg = ExecuteMsg::PruneCommitments {
+            start_after: None,
+            limit: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), msg).unwrap();
+        assert_eq!(res.attributes[1].value, "1");
+
+        assert!(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::CommitmentTimestamp {
+                commitment: String::from("commitment_a"),
+            },
+        )
+        .is_err());
+        assert!(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::CommitmentTimestamp {
+                commitment: String::from("commitment_b"),
+            },
+        )
+        .is_ok());
+
+        // An expired commitment can be committed again
+        let msg = ExecuteMsg::Commit {
+            commitment: String::from("commitment_a"),
+        };
+        execute(deps.as_mut(), env, mock_info("alice", &[]), msg).unwrap();
+    }
+
+    #[test]
+    fn test_register_refunds_overpayment() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let name = String::from("alice");
+        let owner = String::from("alice");
+        let secret = String::from("secret");
+        let duration: u64 = 24 * 3600 * 365;
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MakeCommitment {
+                name: name.clone(),
+                owner: owner.clone(),
+                secret: secret.clone(),
+                resolver: None,
+                address: None,
+            },
+        )
+        .unwrap();
+        let commitment_response: MakeCommitmentResponse = from_binary(&res).unwrap();
+        let msg = ExecuteMsg::Commit {
+            commitment: commitment_response.commitment,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+        let register_msg = ExecuteMsg::Register {
+            name: name.clone(),
+            owner: owner.clone(),
+            duration,
+            secret: secret.clone(),
+            resolver: None,
+            address: None,
+            reverse_record: false,
+            promo_code: None,
+        };
+
+        // Unexpected denom is rejected
+        let info = mock_info(
+            "alice",
+            &[
+                Coin::new(5_000_000u128, "usei"),
+                Coin::new(1_000u128, "uusdc"),
+            ],
+        );
+        let err = execute(deps.as_mut(), env.clone(), info, register_msg.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnexpectedDenom {
+                denom: String::from("uusdc")
+            }
+        );
+
+        let info = mock_info("alice", &coins(7_000_000u128, "usei"));
+        let res = execute(deps.as_mut(), env.clone(), info, register_msg).unwrap();
+        let refund_msg: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("alice"),
+            amount: coins(2_000_000u128, "usei"),
+        });
+        assert_eq!(res.messages.len(), 5); // Register, Set resolver, Reclaim, Transfer NFT, Refund
+        assert_eq!(res.messages[4].msg, refund_msg);
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "refund" && attr.value == "2000000"));
+
+        // Renew with exact price doesn't refund
+        let info = mock_info("alice", &coins(5_000_000u128, "usei"));
+        let msg = ExecuteMsg::Renew {
+            name: name.clone(),
+            duration,
+            promo_code: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap();
+        assert_eq!(res.messages.len(), 1);
+
+        let info = mock_info("alice", &coins(5_500_000u128, "usei"));
+        let res = execute(deps.as_mut(), env, info, msg).unwrap();
+        let refund_msg: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("alice"),
+            amount: coins(500_000u128, "usei"),
+        });
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(res.messages[1].msg, refund_msg);
+    }
+
+    #[test]
+    fn test_price_source() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::Fixed {},
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let name = String::from("abc");
+        let duration: u64 = 24 * 3600 * 365;
+        let rent_price = |deps: &OwnedDeps<_, _, _, _>, env: Env| -> Uint128 {
+            let res = query(
+                deps.as_ref(),
+                env,
+                QueryMsg::RentPrice {
+                    name: name.clone(),
+                    duration,
+                    promo_code: None,
+                },
+            )
+            .unwrap();
+            from_binary::<RentPriceResponse>(&res).unwrap().price
+        };
+        assert_eq!(rent_price(&deps, mock_env()), Uint128::from(640_000_000u128));
+
+        // Price feed returns 0.5 USD per usei
+        let msg = ExecuteMsg::SetPriceSource {
+            price_source: PriceSource::PriceFeed {
+                contract: String::from("price_feed_address"),
+                max_staleness_secs: 3600,
+            },
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg.clone())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NotOwner {
+                sender: String::from("alice"),
+                owner: String::from("creator")
+            }
+        );
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert_eq!(rent_price(&deps, mock_env()), Uint128::from(1_280_000_000u128));
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(3601);
+        let err = get_cost(deps.as_ref(), &env, name.clone(), duration).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::StalePrice {
+                last_update: mock_env().block.time.seconds(),
+                oldest_allowed: mock_env().block.time.seconds() + 1,
+            }
+        );
+
+        // Sei oracle returns 1 USD per usei
+        let msg = ExecuteMsg::SetPriceSource {
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetPriceSource {}).unwrap();
+        let res: PriceSourceResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            res.price_source,
+            PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            }
+        );
+        assert_eq!(rent_price(&deps, mock_env()), Uint128::from(640_000_000u128));
+
+        let mut env = mock_env();
+        env.block.height += 101;
+        let err = get_cost(deps.as_ref(), &env, name, duration).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::StalePrice {
+                last_update: mock_env().block.height,
+                oldest_allowed: mock_env().block.height + 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_price_tiers() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let duration = 24 * 3600 * 365;
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PriceTiers {}).unwrap();
+        let res: PriceTiersResponse = from_binary(&res).unwrap();
+        assert_eq!(res.tiers, price_tiers());
+
+        let err = get_cost(deps.as_ref(), &mock_env(), String::from("ab"), duration).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NameTooShort {
+                length: 2,
+                min_length: 3
+            }
+        );
+
+        // Overlapping tiers are rejected
+        let msg = ExecuteMsg::AddPriceTier {
+            min_length: 2,
+            max_length: Some(3),
+            annual_price: 1_000_000_000u64,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidPriceTier {
+                description: String::from("Tier starting at 2 overlaps the tier starting at 3"),
+            }
+        );
+
+        let msg = ExecuteMsg::AddPriceTier {
+            min_length: 2,
+            max_length: Some(2),
+            annual_price: 1_000_000_000u64,
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NotOwner {
+                sender: String::from("alice"),
+                owner: String::from("creator")
+            }
+        );
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let cost = get_cost(deps.as_ref(), &mock_env(), String::from("ab"), duration).unwrap();
+        assert_eq!(cost, Uint128::from(1_000_000_000u128));
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::IsValidName {
+                name: String::from("ab"),
+            },
+        )
+        .unwrap();
+        assert!(
+            from_binary::<IsValidNameResponse>(&res)
+                .unwrap()
+                .is_valid_name
+        );
+
+        // Cap the open-ended tier and price longer names separately
+        let msg = ExecuteMsg::UpdatePriceTier {
+            min_length: 5,
+            max_length: Some(9),
+            annual_price: 10_000_000u64,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let cost = get_cost(deps.as_ref(), &mock_env(), String::from("abcde"), duration).unwrap();
+        assert_eq!(cost, Uint128::from(10_000_000u128));
+        let err = get_cost(
+            deps.as_ref(),
+            &mock_env(),
+            String::from("abcdefghij"),
+            duration,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NoPriceTier { length: 10 });
+
+        let msg = ExecuteMsg::AddPriceTier {
+            min_length: 10,
+            max_length: None,
+            annual_price: 1_000_000u64,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let cost = get_cost(
+            deps.as_ref(),
+            &mock_env(),
+            String::from("abcdefghij"),
+            duration,
+        )
+        .unwrap();
+        assert_eq!(cost, Uint128::from(1_000_000u128));
+
+        let msg = ExecuteMsg::RemovePriceTier { min_length: 4 };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let err = get_cost(deps.as_ref(), &mock_env(), String::from("abcd"), duration).unwrap_err();
+        assert_eq!(err, ContractError::NoPriceTier { length: 4 });
+
+        let msg = ExecuteMsg::RemovePriceTier { min_length: 4 };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::NoPriceTier { length: 4 });
+    }
+
+    #[test]
+    fn test_premium_names() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let duration: u64 = 24 * 3600 * 365;
+
+        let msg = ExecuteMsg::SetPremiumName {
+            name: String::from("brand"),
+            premium: PremiumName::Reserved {},
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg.clone())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NotOwner {
+                sender: String::from("alice"),
+                owner: String::from("creator")
+            }
+        );
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let msg = ExecuteMsg::SetPremiumName {
+            name: String::from("crypto"),
+            premium: PremiumName::Price {
+                annual_price: 100_000_000u64,
+            },
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        // Premium price replaces the length tier price
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::RentPrice {
+                name: String::from("crypto"),
+                duration,
+                promo_code: None,
+            },
+        )
+        .unwrap();
+        let res: RentPriceResponse = from_binary(&res).unwrap();
+        assert_eq!(res.price, Uint128::from(100_000_000u128));
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::IsValidName {
+                name: String::from("brand"),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            from_binary::<IsValidNameResponse>(&res).unwrap(),
+            IsValidNameResponse {
+                is_valid_name: true,
+                is_reserved: true,
+                failed_rule: None,
+            }
+        );
+
+        let msg = QueryMsg::PremiumNames {
+            start_after: None,
+            limit: Some(1),
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let res: PremiumNamesResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            res.names,
+            vec![PremiumNameInfo {
+                name: String::from("brand"),
+                premium: PremiumName::Reserved {},
+            }]
+        );
+        let msg = QueryMsg::PremiumNames {
+            start_after: Some(String::from("brand")),
+            limit: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let res: PremiumNamesResponse = from_binary(&res).unwrap();
+        assert_eq!(res.names.len(), 1);
+        assert_eq!(res.names[0].name, String::from("crypto"));
+
+        // Reserved names can't be registered by anyone but the owner
+        let name = String::from("brand");
+        let owner = String::from("alice");
+        let secret = String::from("secret");
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MakeCommitment {
+                name: name.clone(),
+                owner: owner.clone(),
+                secret: secret.clone(),
+                resolver: None,
+                address: None,
+            },
+        )
+        .unwrap();
+        let commitment_response: MakeCommitmentResponse = from_binary(&res).unwrap();
+        let msg = ExecuteMsg::Commit {
+            commitment: commitment_response.commitment,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+        let msg = ExecuteMsg::Register {
+            name: name.clone(),
+            owner: owner.clone(),
+            duration,
+            secret,
+            resolver: None,
+            address: None,
+            reverse_record: false,
+            promo_code: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &coins(640_000_000u128, "usei")),
+            msg,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::ReservedName { name: name.clone() });
+
+        // Renewals are priced by the canonical label, whatever the case of the input
+        let msg = ExecuteMsg::Renew {
+            name: String::from("BRAND"),
+            duration,
+            promo_code: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &coins(640_000_000u128, "usei")),
+            msg,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::ReservedName { name: name.clone() });
+
+        let msg = ExecuteMsg::OwnerRegister {
+            name: name.clone(),
+            owner,
+            duration,
+            resolver: None,
+            address: None,
+            reverse_record: false,
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::RemovePremiumName { name: name.clone() };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::RentPrice {
+                name,
+                duration,
+                promo_code: None,
+            },
+        )
+        .unwrap();
+        let res: RentPriceResponse = from_binary(&res).unwrap();
+        assert_eq!(res.price, Uint128::from(5_000_000u128));
+    }
+
+    #[test]
+    fn test_expiry_premium() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: Some(ExpiryPremium {
+                start_price: 1_000_000_000u64,
+                duration: 10 * 86400,
+                decay: PremiumDecay::Linear {},
+            }),
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let rent_price = |deps: &OwnedDeps<_, _, _, _>, seconds: u64| -> RentPriceResponse {
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(seconds);
+            let msg = QueryMsg::RentPrice {
+                name: String::from("abcde"),
+                duration: 24 * 3600 * 365,
+                promo_code: None,
+            };
+            from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap()
+        };
+
+        // Names are released at mock_env time
+        let res = rent_price(&deps, 0);
+        assert_eq!(res.premium, Uint128::zero());
+        assert_eq!(res.price, Uint128::from(5_000_000u128));
+
+        let res = rent_price(&deps, 5 * 86400);
+        assert_eq!(res.premium, Uint128::from(500_000_000u128));
+        assert_eq!(res.price, Uint128::from(505_000_000u128));
+        let res = rent_price(&deps, 10 * 86400);
+        assert_eq!(res.premium, Uint128::zero());
+
+        let msg = ExecuteMsg::SetExpiryPremium {
+            expiry_premium: Some(ExpiryPremium {
+                start_price: 1_000_000_000u64,
+                duration: 10 * 86400,
+                decay: PremiumDecay::Exponential { half_life: 0 },
+            }),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BadRequest {
+                msg: String::from("Expiry premium half_life must be greater than zero"),
+            }
+        );
+
+        let expiry_premium = ExpiryPremium {
+            start_price: 1_000_000_000u64,
+            duration: 10 * 86400,
+            decay: PremiumDecay::Exponential { half_life: 86400 },
+        };
+        let msg = ExecuteMsg::SetExpiryPremium {
+            expiry_premium: Some(expiry_premium.clone()),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg.clone())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NotOwner {
+                sender: String::from("alice"),
+                owner: String::from("creator")
+            }
+        );
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetExpiryPremium {}).unwrap();
+        let res: ExpiryPremiumResponse = from_binary(&res).unwrap();
+        assert_eq!(res.expiry_premium, Some(expiry_premium));
+
+        let res = rent_price(&deps, 1);
+        assert_eq!(res.premium, Uint128::from(999_994_213u128));
+        let res = rent_price(&deps, 86400);
+        assert_eq!(res.premium, Uint128::from(500_000_000u128));
+        let res = rent_price(&deps, 86400 + 43200);
+        assert_eq!(res.premium, Uint128::from(375_000_000u128));
+        let res = rent_price(&deps, 2 * 86400);
+        assert_eq!(res.premium, Uint128::from(250_000_000u128));
+        let res = rent_price(&deps, 10 * 86400);
+        assert_eq!(res.premium, Uint128::zero());
+    }
+
+    #[test]
+    fn test_auction() {
+        let mut deps = mock_dependencies(&coins(1_000_000_000u128, "usei"));
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let name = String::from("abc");
+
+        let msg = ExecuteMsg::StartAuction {
+            name: name.clone(),
+            reserve_price: Uint128::from(700_000_000u128),
+            min_increment: Uint128::from(10_000_000u128),
+            bidding_period: 86400,
+            extension: 600,
+            duration: 24 * 3600 * 365,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg.clone())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NotOwner {
+                sender: String::from("alice"),
+                owner: String::from("creator")
+            }
+        );
+        let zero_period = ExecuteMsg::StartAuction {
+            name: name.clone(),
+            reserve_price: Uint128::from(700_000_000u128),
+            min_increment: Uint128::from(10_000_000u128),
+            bidding_period: 0,
+            extension: 600,
+            duration: 24 * 3600 * 365,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), zero_period).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BadRequest {
+                msg: String::from("bidding_period must be greater than zero")
+            }
+        );
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        // The name can't be registered while it's in auction
+        let msg = ExecuteMsg::Register {
+            name: name.clone(),
+            owner: String::from("alice"),
+            duration: 24 * 3600 * 365,
+            secret: String::from("secret"),
+            resolver: None,
+            address: None,
+            reverse_record: false,
+            promo_code: None,
+        };
+        let info_alice = mock_info("alice", &coins(640_000_000u128, "usei"));
+        let err = execute(deps.as_mut(), mock_env(), info_alice, msg).unwrap_err();
+        assert_eq!(err, ContractError::NameInAuction { name: name.clone() });
+        let msg = ExecuteMsg::OwnerRegister {
+            name: name.clone(),
+            owner: String::from("creator"),
+            duration: 24 * 3600 * 365,
+            resolver: None,
+            address: None,
+            reverse_record: false,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::NameInAuction { name: name.clone() });
+
+        let msg = ExecuteMsg::Bid { name: name.clone() };
+        let info_alice = mock_info("alice", &coins(600_000_000u128, "usei"));
+        let err = execute(deps.as_mut(), mock_env(), info_alice, msg.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BidTooLow {
+                amount: Uint128::from(600_000_000u128),
+                min_bid: Uint128::from(700_000_000u128),
+            }
+        );
+        let info_alice = mock_info("alice", &coins(700_000_000u128, "usei"));
+        execute(deps.as_mut(), mock_env(), info_alice, msg.clone()).unwrap();
+
+        let info_bob = mock_info("bob", &coins(705_000_000u128, "usei"));
+        let err = execute(deps.as_mut(), mock_env(), info_bob, msg.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BidTooLow {
+                amount: Uint128::from(705_000_000u128),
+                min_bid: Uint128::from(710_000_000u128),
+            }
+        );
+
+        // Outbid funds are refunded and a late bid extends the auction
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(86400 - 60);
+        let info_bob = mock_info("bob", &coins(750_000_000u128, "usei"));
+        let res = execute(deps.as_mut(), env.clone(), info_bob, msg.clone()).unwrap();
+        let refund_msg: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("alice"),
+            amount: coins(700_000_000u128, "usei"),
+        });
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].msg, refund_msg);
+
+        // Any spelling of the name finds its auction
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Auction {
+                name: String::from("ABC"),
+            },
+        )
+        .unwrap();
+        let res: AuctionResponse = from_binary(&res).unwrap();
+        assert_eq!(res.name, name);
+        assert_eq!(res.auction.end_time, env.block.time.seconds() + 600);
+        assert_eq!(
+            res.auction.highest_bid,
+            Some(Bid {
+                bidder: Addr::unchecked("bob"),
+                amount: Uint128::from(750_000_000u128),
+            })
+        );
+
+        // Escrowed bids are not withdrawn
+        let msg = ExecuteMsg::Withdraw {
+            denom: None,
+            amount: None,
+            recipient: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let withdraw_msg: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("creator"),
+            amount: coins(250_000_000u128, "usei"),
+        });
+        assert_eq!(res.messages[0].msg, withdraw_msg);
+
+        let msg = ExecuteMsg::SettleAuction { name: name.clone() };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::AuctionNotEnded {
+                end_time: env.block.time.seconds() + 600,
+                current: env.block.time.seconds(),
+            }
+        );
+        env.block.time = env.block.time.plus_seconds(600);
+        let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 4); // Register, Set resolver, Reclaim, Transfer NFT
+        assert_eq!(
+            res.messages[3].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("registrar_address"),
+                msg: to_binary(&RegistrarExecuteMsg::<Extension>::TransferNft {
+                    recipient: String::from("bob"),
+                    token_id: String::from(
+                        "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+                    ),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Auctions {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let res: AuctionsResponse = from_binary(&res).unwrap();
+        assert_eq!(res.auctions, vec![]);
+        let msg = ExecuteMsg::Withdraw {
+            denom: None,
+            amount: None,
+            recipient: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let withdraw_msg: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("creator"),
+            amount: coins(1_000_000_000u128, "usei"),
+        });
+        assert_eq!(res.messages[0].msg, withdraw_msg);
+    }
+
+    #[test]
+    fn test_cw20_payment() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        // One token is worth half a price unit
+        let msg = ExecuteMsg::SetCw20Token {
+            token: String::from("cw20_token_address"),
+            rate: Decimal::percent(50),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg.clone())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NotOwner {
+                sender: String::from("alice"),
+                owner: String::from("creator")
+            }
+        );
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let msg = QueryMsg::Cw20Tokens {
+            start_after: None,
+            limit: None,
+        };
+        let res: Cw20TokensResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(
+            res.tokens,
+            vec![Cw20TokenInfo {
+                token: Addr::unchecked("cw20_token_address"),
+                rate: Decimal::percent(50),
+            }]
+        );
+
+        let name = String::from("alice");
+        let owner = String::from("alice");
+        let secret = String::from("secret");
+        let duration: u64 = 24 * 3600 * 365;
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MakeCommitment {
+                name: name.clone(),
+                owner: owner.clone(),
+                secret: secret.clone(),
+                resolver: None,
+                address: None,
+            },
+        )
+        .unwrap();
+        let commitment_response: MakeCommitmentResponse = from_binary(&res).unwrap();
+        let msg = ExecuteMsg::Commit {
+            commitment: commitment_response.commitment,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+        let register_msg = to_binary(&ReceiveMsg::Register {
+            name: name.clone(),
+            owner,
+            duration,
+            secret,
+            resolver: None,
+            address: None,
+            reverse_record: false,
+        })
+        .unwrap();
+
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("alice"),
+            amount: Uint128::from(12_000_000u128),
+            msg: register_msg.clone(),
+        });
+        let err = execute(deps.as_mut(), env.clone(), mock_info("other_token", &[]), msg.clone())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnsupportedToken {
+                token: String::from("other_token")
+            }
+        );
+
+        let token_info = mock_info("cw20_token_address", &[]);
+        let res = execute(deps.as_mut(), env.clone(), token_info.clone(), msg).unwrap();
+        let refund_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("cw20_token_address"),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: String::from("alice"),
+                amount: Uint128::from(2_000_000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        });
+        assert_eq!(res.messages.len(), 5); // Register, Set resolver, Reclaim, Transfer NFT, Refund
+        assert_eq!(res.messages[4].msg, refund_msg);
+
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("alice"),
+            amount: Uint128::from(9_000_000u128),
+            msg: to_binary(&ReceiveMsg::Renew { name, duration }).unwrap(),
+        });
+        let err = execute(deps.as_mut(), env, token_info, msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientFund {
+                amount: Uint128::from(9_000_000u128),
+                required: Uint128::from(10_000_000u128),
+            }
+        );
+
+        // Withdraw sweeps CW20 balances
+        let msg = ExecuteMsg::Withdraw {
+            denom: None,
+            amount: None,
+            recipient: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let transfer_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("cw20_token_address"),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: String::from("creator"),
+                amount: Uint128::from(1_000_000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        });
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].msg, transfer_msg);
+
+        // Removing a token sweeps its balance to the treasury
+        let msg = ExecuteMsg::RemoveCw20Token {
+            token: String::from("cw20_token_address"),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].msg, transfer_msg);
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnsupportedToken {
+                token: String::from("cw20_token_address")
+            }
+        );
+    }
+
+    #[test]
+    fn test_accepted_denoms() {
+        let mut deps = mock_dependencies(&[
+            Coin::new(1_000_000u128, "usei"),
+            Coin::new(3_000_000u128, "uusdc"),
+        ]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![AcceptedDenom {
+                denom: String::from("uusdc"),
+                price_source: PriceSource::Fixed {},
+            }],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: Some(String::from("treasury")),
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        // The oracle has no rate for uatom, so it is left out of the quotes
+        let msg = ExecuteMsg::SetAcceptedDenom {
+            denom: String::from("uatom"),
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::AcceptedDenoms {}).unwrap();
+        let res: AcceptedDenomsResponse = from_binary(&res).unwrap();
+        assert_eq!(res.denoms.len(), 2);
+
+        let name = String::from("alice");
+        let duration: u64 = 24 * 3600 * 365;
+        let msg = QueryMsg::RentPrice {
+            name: name.clone(),
+            duration,
+            promo_code: None,
+        };
+        let res: RentPriceResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(
+            res.quotes,
+            vec![
+                Coin::new(5_000_000u128, "usei"),
+                Coin::new(5_000_000u128, "uusdc")
+            ]
+        );
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MakeCommitment {
+                name: name.clone(),
+                owner: String::from("alice"),
+                secret: String::from("secret"),
+                resolver: None,
+                address: None,
+            },
+        )
+        .unwrap();
+        let commitment_response: MakeCommitmentResponse = from_binary(&res).unwrap();
+        let msg = ExecuteMsg::Commit {
+            commitment: commitment_response.commitment,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+        let msg = ExecuteMsg::Register {
+            name,
+            owner: String::from("alice"),
+            duration,
+            secret: String::from("secret"),
+            resolver: None,
+            address: None,
+            reverse_record: false,
+            promo_code: None,
+        };
+        let funds = vec![
+            Coin::new(3_000_000u128, "usei"),
+            Coin::new(3_000_000u128, "uusdc"),
+        ];
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &funds), msg.clone())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BadRequest {
+                msg: String::from("Payment must be made in a single denom")
+            }
+        );
+        let funds = coins(6_000_000, "ufoo");
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &funds), msg.clone())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnexpectedDenom {
+                denom: String::from("ufoo")
+            }
+        );
+
+        let funds = coins(6_000_000, "uusdc");
+        let res = execute(deps.as_mut(), env, mock_info("alice", &funds), msg).unwrap();
+        let refund_msg: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("alice"),
+            amount: coins(1_000_000, "uusdc"),
+        });
+        assert_eq!(res.messages.len(), 5); // Register, Set resolver, Reclaim, Transfer NFT, Refund
+        assert_eq!(res.messages[4].msg, refund_msg);
+
+        // Withdraw a specific amount to the treasury
+        let msg = ExecuteMsg::Withdraw {
+            denom: Some(String::from("uusdc")),
+            amount: Some(Uint128::from(4_000_000u128)),
+            recipient: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientFund {
+                amount: Uint128::from(3_000_000u128),
+                required: Uint128::from(4_000_000u128),
+            }
+        );
+        let msg = ExecuteMsg::Withdraw {
+            denom: Some(String::from("uusdc")),
+            amount: Some(Uint128::from(2_000_000u128)),
+            recipient: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let withdraw_msg: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("treasury"),
+            amount: coins(2_000_000, "uusdc"),
+        });
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].msg, withdraw_msg);
+
+        let msg = ExecuteMsg::SetTreasury {
+            treasury: String::from("new_treasury"),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Treasury {}).unwrap();
+        let res: TreasuryResponse = from_binary(&res).unwrap();
+        assert_eq!(res.treasury, Addr::unchecked("new_treasury"));
+
+        // Sweep every accepted denom, uatom has no balance
+        let msg = ExecuteMsg::Withdraw {
+            denom: None,
+            amount: None,
+            recipient: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let usei_msg: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("new_treasury"),
+            amount: coins(1_000_000, "usei"),
+        });
+        let uusdc_msg: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("new_treasury"),
+            amount: coins(3_000_000, "uusdc"),
+        });
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(res.messages[0].msg, usei_msg);
+        assert_eq!(res.messages[1].msg, uusdc_msg);
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("coins", "1000000usei,3000000uusdc")));
+    }
+
+    #[test]
+    fn test_fee_split() {
+        let mut deps = mock_dependencies(&[Coin::new(1_000_000u128, "usei")]);
+        let fee_split = FeeSplit {
+            shares: vec![
+                FeeShare {
+                    recipient: FeeRecipient::Address {
+                        address: Addr::unchecked("dao"),
+                    },
+                    bps: 5_000,
+                },
+                FeeShare {
+                    recipient: FeeRecipient::Address {
+                        address: Addr::unchecked("dev_fund"),
+                    },
+                    bps: 3_000,
+                },
+                FeeShare {
+                    recipient: FeeRecipient::Burn {},
+                    bps: 2_000,
+                },
+            ],
+            distribute_on_register: false,
+        };
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: Some(fee_split.clone()),
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let mut invalid_split = fee_split.clone();
+        invalid_split.shares.pop();
+        let msg = ExecuteMsg::SetFeeSplit {
+            fee_split: Some(invalid_split),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidFeeSplit {
+                description: String::from("Shares add up to 8000 bps instead of 10000")
+            }
+        );
+
+        // Anyone can distribute the accumulated revenue
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::Distribute {},
+        )
+        .unwrap();
+        let expected: Vec<CosmosMsg> = vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("dao"),
+                amount: coins(500_000, "usei"),
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("dev_fund"),
+                amount: coins(300_000, "usei"),
+            }),
+            CosmosMsg::Bank(BankMsg::Burn {
+                amount: coins(200_000, "usei"),
+            }),
+        ];
+        let messages: Vec<CosmosMsg> = res.messages.into_iter().map(|sub| sub.msg).collect();
+        assert_eq!(messages, expected);
+
+        // Partial withdrawal to an explicit recipient
+        let msg = ExecuteMsg::Withdraw {
+            denom: Some(String::from("usei")),
+            amount: Some(Uint128::from(100_000u128)),
+            recipient: Some(String::from("dev_fund")),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let withdraw_msg: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("dev_fund"),
+            amount: coins(100_000, "usei"),
+        });
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].msg, withdraw_msg);
+
+        // Split every payment as it comes in
+        let mut fee_split = fee_split;
+        fee_split.distribute_on_register = true;
+        let msg = ExecuteMsg::SetFeeSplit {
+            fee_split: Some(fee_split.clone()),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetFeeSplit {}).unwrap();
+        let res: FeeSplitResponse = from_binary(&res).unwrap();
+        assert_eq!(res.fee_split, Some(fee_split));
+
+        let name = String::from("alice");
+        let duration: u64 = 24 * 3600 * 365;
+        let msg = ExecuteMsg::Renew {
+            name,
+            duration,
+            promo_code: None,
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &coins(5_000_000, "usei")),
+            msg,
+        )
+        .unwrap();
+        let expected: Vec<CosmosMsg> = vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("dao"),
+                amount: coins(2_500_000, "usei"),
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("dev_fund"),
+                amount: coins(1_500_000, "usei"),
+            }),
+            CosmosMsg::Bank(BankMsg::Burn {
+                amount: coins(1_000_000, "usei"),
+            }),
+        ];
+        let messages: Vec<CosmosMsg> = res.messages.into_iter().map(|sub| sub.msg).collect();
+        assert_eq!(messages.len(), 4); // Renew and the split
+        assert_eq!(messages[1..], expected);
+
+        // The winning bid of an auction is split as well
+        let name = String::from("bobby");
+        let msg = ExecuteMsg::StartAuction {
+            name: name.clone(),
+            reserve_price: Uint128::from(5_000_000u128),
+            min_increment: Uint128::from(1_000_000u128),
+            bidding_period: 86400,
+            extension: 600,
+            duration,
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let msg = ExecuteMsg::Bid { name: name.clone() };
+        let info_bob = mock_info("bob", &coins(5_000_000, "usei"));
+        execute(deps.as_mut(), mock_env(), info_bob, msg).unwrap();
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(86400);
+        let msg = ExecuteMsg::SettleAuction { name };
+        let res = execute(deps.as_mut(), env, mock_info("anyone", &[]), msg).unwrap();
+        let messages: Vec<CosmosMsg> = res.messages.into_iter().map(|sub| sub.msg).collect();
+        assert_eq!(messages.len(), 7); // Register, Set resolver, Reclaim, Transfer NFT and the split
+        assert_eq!(messages[4..], expected);
+    }
+
+    #[test]
+    fn test_referral_rewards() {
+        let mut deps = mock_dependencies(&[Coin::new(10_000_000u128, "usei")]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+        let duration: u64 = 24 * 3600 * 365;
+        for name in ["alice", "carol"] {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::MakeCommitment {
+                    name: String::from(name),
+                    owner: String::from("alice"),
+                    secret: String::from("secret"),
+                    resolver: None,
+                    address: None,
+                },
+            )
+            .unwrap();
+            let commitment_response: MakeCommitmentResponse = from_binary(&res).unwrap();
+            let msg = ExecuteMsg::Commit {
+                commitment: commitment_response.commitment,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+        }
+
+        // The reward is credited to the owner of "bob" instead of being sent
+        let msg = ExecuteMsg::ReferalRegister {
+            name: String::from("alice"),
+            owner: String::from("alice"),
+            duration,
+            secret: String::from("secret"),
+            resolver: None,
+            address: None,
+            referer: Some(String::from("bob")),
+            reverse_record: false,
+        };
+        let funds = coins(5_000_000, "usei");
+        let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &funds), msg).unwrap();
+        assert_eq!(res.messages.len(), 4); // Register, Set resolver, Reclaim, Transfer NFT
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("referal_owner", "referrer")));
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("referal_fund", "1000000")));
+
+        // A referrer without an owner does not block the registration
+        let msg = ExecuteMsg::ReferalRegister {
+            name: String::from("carol"),
+            owner: String::from("alice"),
+            duration,
+            secret: String::from("secret"),
+            resolver: None,
+            address: None,
+            referer: Some(String::from("unknown")),
+            reverse_record: false,
+        };
+        let res = execute(deps.as_mut(), env, mock_info("alice", &funds), msg).unwrap();
+        assert!(res.attributes.contains(&Attribute::new(
+            "referal_error",
+            "ReferrerNotFound: unknown does not resolve to an owner."
+        )));
+
+        let msg = QueryMsg::ReferralStats {
+            name: String::from("bob"),
+        };
+        let res: ReferralStatsResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(
+            res.stats,
+            ReferralStats {
+                referrals: 1,
+                volume: coins(5_000_000, "usei"),
+                rewards: coins(1_000_000, "usei"),
+                unclaimed: coins(1_000_000, "usei"),
+            }
+        );
+        let msg = QueryMsg::ReferralRewards {
+            address: String::from("referrer"),
+        };
+        let res: ReferralRewardsResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.unclaimed, coins(1_000_000, "usei"));
+
+        // Unclaimed rewards are not withdrawn
+        let msg = ExecuteMsg::Withdraw {
+            denom: None,
+            amount: None,
+            recipient: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let withdraw_msg: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("creator"),
+            amount: coins(9_000_000, "usei"),
+        });
+        assert_eq!(res.messages[0].msg, withdraw_msg);
+
+        let referrer = mock_info("referrer", &[]);
+        let msg = ExecuteMsg::ClaimReferralRewards {};
+        let res = execute(deps.as_mut(), mock_env(), referrer.clone(), msg.clone()).unwrap();
+        let claim_msg: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("referrer"),
+            amount: coins(1_000_000, "usei"),
+        });
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].msg, claim_msg);
+        // The claim clears the unclaimed balance of the name, not its lifetime totals
+        let res: ReferralStatsResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ReferralStats {
+                    name: String::from("bob"),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.stats.rewards, coins(1_000_000, "usei"));
+        assert_eq!(res.stats.unclaimed, vec![]);
+        let err = execute(deps.as_mut(), mock_env(), referrer, msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BadRequest {
+                msg: String::from("No referral rewards to claim")
+            }
+        );
+    }
+
+    #[test]
+    fn test_referral_tiers() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let tiers = vec![
+            ReferralTier {
+                min_referrals: 0,
+                percentage: 10,
+            },
+            ReferralTier {
+                min_referrals: 1,
+                percentage: 15,
+            },
+        ];
+        let msg = ExecuteMsg::SetReferralTiers {
+            tiers: vec![ReferralTier {
+                min_referrals: 0,
+                percentage: 60,
+            }],
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::ReferalPercentageError {
+                description: Some(String::from("Tier percentage must be in 0~50"))
+            }
+        );
+        let msg = ExecuteMsg::SetReferralTiers {
+            tiers: tiers.clone(),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ReferralTiers {}).unwrap();
+        let res: ReferralTiersResponse = from_binary(&res).unwrap();
+        assert_eq!(res.tiers, tiers);
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+        let duration: u64 = 24 * 3600 * 365;
+        // The second referral is rewarded with the next tier
+        for (name, referal_fund) in [("alice", "500000"), ("carol", "750000")] {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::MakeCommitment {
+                    name: String::from(name),
+                    owner: String::from("alice"),
+                    secret: String::from("secret"),
+                    resolver: None,
+                    address: None,
+                },
+            )
+            .unwrap();
+            let commitment_response: MakeCommitmentResponse = from_binary(&res).unwrap();
+            let msg = ExecuteMsg::Commit {
+                commitment: commitment_response.commitment,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+
+            let msg = ExecuteMsg::ReferalRegister {
+                name: String::from(name),
+                owner: String::from("alice"),
+                duration,
+                secret: String::from("secret"),
+                resolver: None,
+                address: None,
+                referer: Some(String::from("bob")),
+                reverse_record: false,
+            };
+            let funds = coins(5_000_000, "usei");
+            let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &funds), msg).unwrap();
+            assert!(res
+                .attributes
+                .contains(&Attribute::new("referal_fund", referal_fund)));
+        }
+
+        let msg = QueryMsg::ReferralTier {
+            name: String::from("bob"),
+        };
+        let res: ReferralTierResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.referrals, 2);
+        assert_eq!(res.tier, Some(tiers[1].clone()));
+        assert_eq!(res.percentage, 15);
+    }
+
+    #[test]
+    fn test_whitelist() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        // Every name is owned by "referrer" in the mock registry
+        let msg = ExecuteMsg::AddWhiteList {
+            ensname: String::from("bob"),
+        };
+        let funds = coins(640_000_000, "usei");
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &funds), msg.clone())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Unauthorized {
+                description: Some(String::from("bob is not owned by alice"))
+            }
+        );
+        execute(deps.as_mut(), mock_env(), mock_info("referrer", &funds), msg).unwrap();
+
+        let expires = mock_env().block.time.seconds() + 100;
+        let msg = ExecuteMsg::AddWhiteListByOwner {
+            ensname: String::from("carol"),
+            referal_percentage: Some(30),
+            expires: Some(expires),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = QueryMsg::WhiteList {
+            start_after: None,
+            limit: Some(1),
+        };
+        let res: WhiteListResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.entries.len(), 1);
+        assert_eq!(res.entries[0].name, "bob");
+        assert_eq!(res.entries[0].owner, Addr::unchecked("referrer"));
+        assert_eq!(res.entries[0].percentage, 40);
+        let msg = QueryMsg::WhiteList {
+            start_after: Some(String::from("bob")),
+            limit: None,
+        };
+        let res: WhiteListResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.entries.len(), 1);
+        assert_eq!(res.entries[0].name, "carol");
+        assert_eq!(res.entries[0].expires, Some(expires));
+
+        // The entry stops applying once it expires
+        let msg = QueryMsg::ReferralTier {
+            name: String::from("carol"),
+        };
+        let res: ReferralTierResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg.clone()).unwrap()).unwrap();
+        assert_eq!(res.percentage, 30);
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(200);
+        let res: ReferralTierResponse =
+            from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+        assert_eq!(res.percentage, 20);
+
+        let msg = ExecuteMsg::RemoveWhiteList {
+            ensname: String::from("bob"),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg.clone())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NotOwner {
+                sender: String::from("alice"),
+                owner: String::from("creator")
+            }
+        );
+        execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BadRequest {
+                msg: String::from("bob is not whitelisted")
+            }
+        );
+        let msg = QueryMsg::WhiteList {
+            start_after: None,
+            limit: None,
+        };
+        let res: WhiteListResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.entries.len(), 1);
+        assert_eq!(res.entries[0].name, "carol");
+    }
+
+    #[test]
+    fn test_promo_codes() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let code_hash = hex::encode(keccak256(b"LAUNCH"));
+        let promo_code = PromoCode {
+            discount: Discount::Percentage { percentage: 20 },
+            max_uses: Some(10),
+            max_uses_per_address: Some(1),
+            start_time: None,
+            end_time: None,
+            min_length: Some(5),
+            max_length: None,
+        };
+        let msg = ExecuteMsg::SetPromoCode {
+            code_hash: String::from("LAUNCH"),
+            promo_code: promo_code.clone(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidPromoCode {
+                description: String::from("code_hash must be a hex encoded keccak256 hash")
+            }
+        );
+        let msg = ExecuteMsg::SetPromoCode {
+            code_hash: code_hash.clone(),
+            promo_code: promo_code.clone(),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let duration: u64 = 24 * 3600 * 365;
+        let msg = QueryMsg::RentPrice {
+            name: String::from("alice"),
+            duration,
+            promo_code: Some(String::from("LAUNCH")),
+        };
+        let res: RentPriceResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.price, Uint128::from(4_000_000u128));
+        assert_eq!(res.discount, Uint128::from(1_000_000u128));
+        // The code only applies to names of at least 5 characters
+        let msg = QueryMsg::RentPrice {
+            name: String::from("bob"),
+            duration,
+            promo_code: Some(String::from("LAUNCH")),
+        };
+        query(deps.as_ref(), mock_env(), msg).unwrap_err();
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MakeCommitment {
+                name: String::from("alice"),
+                owner: String::from("alice"),
+                secret: String::from("secret"),
+                resolver: None,
+                address: None,
+            },
+        )
+        .unwrap();
+        let commitment_response: MakeCommitmentResponse = from_binary(&res).unwrap();
+        let msg = ExecuteMsg::Commit {
+            commitment: commitment_response.commitment,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+        let msg = ExecuteMsg::Register {
+            name: String::from("alice"),
+            owner: String::from("alice"),
+            duration,
+            secret: String::from("secret"),
+            resolver: None,
+            address: None,
+            reverse_record: false,
+            promo_code: Some(String::from("LAUNCH")),
+        };
+        let funds = coins(4_000_000, "usei");
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &funds), msg).unwrap();
+
+        let msg = ExecuteMsg::Renew {
+            name: String::from("alice"),
+            duration,
+            promo_code: Some(String::from("LAUNCH")),
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &funds), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidPromoCode {
+                description: String::from("alice has used the promo code too many times")
+            }
+        );
+
+        let msg = QueryMsg::PromoCode {
+            code_hash: code_hash.clone(),
+        };
+        let res: PromoCodeResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.code_hash, code_hash);
+        assert_eq!(res.promo_code, promo_code);
+        assert_eq!(res.uses, 1);
+    }
+
+    #[test]
+    fn test_claim_with_proof() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        // Tree with the two leaves alice and bob
+        let alice_leaf = keccak256(b"alice");
+        let bob_leaf = keccak256(b"bob");
+        let merkle_root = if alice_leaf <= bob_leaf {
+            keccak256(&[alice_leaf.clone(), bob_leaf.clone()].concat())
+        } else {
+            keccak256(&[bob_leaf.clone(), alice_leaf.clone()].concat())
+        };
+        let merkle_campaign = MerkleCampaign {
+            merkle_root: hex::encode(merkle_root),
+            duration: 24 * 3600 * 365,
+            discount: None,
+            end_time: None,
+        };
+        let msg = ExecuteMsg::SetMerkleCampaign {
+            campaign: String::from("airdrop"),
+            merkle_campaign: merkle_campaign.clone(),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let msg = QueryMsg::MerkleCampaign {
+            campaign: String::from("airdrop"),
+        };
+        let res: MerkleCampaignResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.merkle_campaign, merkle_campaign);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MakeCommitment {
+                name: String::from("alice"),
+                owner: String::from("alice"),
+                secret: String::from("secret"),
+                resolver: None,
+                address: None,
+            },
+        )
+        .unwrap();
+        let commitment_response: MakeCommitmentResponse = from_binary(&res).unwrap();
+        let msg = ExecuteMsg::Commit {
+            commitment: commitment_response.commitment,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+
+        // Reserved names stay owner-only, and premium names are not free
+        let creator = mock_info("creator", &[]);
+        let msg = ExecuteMsg::SetPremiumName {
+            name: String::from("brand"),
+            premium: PremiumName::Reserved {},
+        };
+        execute(deps.as_mut(), mock_env(), creator.clone(), msg).unwrap();
+        let msg = ExecuteMsg::SetPremiumName {
+            name: String::from("crypto"),
+            premium: PremiumName::Price {
+                annual_price: 100_000_000u64,
+            },
+        };
+        execute(deps.as_mut(), mock_env(), creator, msg).unwrap();
+        let bob_claim = |name: &str| ExecuteMsg::ClaimWithProof {
+            campaign: String::from("airdrop"),
+            name: String::from(name),
+            owner: String::from("bob"),
+            secret: String::from("secret"),
+            resolver: None,
+            address: None,
+            reverse_record: false,
+            proof: vec![hex::encode(&alice_leaf)],
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bob", &[]),
+            bob_claim("brand"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::ReservedName {
+                name: String::from("brand")
+            }
+        );
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bob", &[]),
+            bob_claim("crypto"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::PremiumNotClaimable {
+                name: String::from("crypto")
+            }
+        );
+
+        let msg = ExecuteMsg::ClaimWithProof {
+            campaign: String::from("airdrop"),
+            name: String::from("alice"),
+            owner: String::from("alice"),
+            secret: String::from("secret"),
+            resolver: None,
+            address: None,
+            reverse_record: false,
+            proof: vec![hex::encode(&bob_leaf)],
+        };
+        // The proof of alice does not hold for carol
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("carol", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidMerkleProof {
+                campaign: String::from("airdrop"),
+                address: String::from("carol")
+            }
+        );
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &[]),
+            msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 4);
+        let err = execute(deps.as_mut(), env, mock_info("alice", &[]), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::AlreadyClaimed {
+                campaign: String::from("airdrop"),
+                address: String::from("alice")
+            }
+        );
+
+        let msg = QueryMsg::IsClaimed {
+            campaign: String::from("airdrop"),
+            address: String::from("alice"),
+        };
+        let res: IsClaimedResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert!(res.claimed);
+    }
+
+    #[test]
+    fn test_batch_register_and_renew() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let duration: u64 = 24 * 3600 * 365;
+        let mut registrations: Vec<RegisterRequest> = vec![];
+        for (index, name) in ["alice", "carol", "alice"].iter().enumerate() {
+            let registration = RegisterRequest {
+                name: name.to_string(),
+                owner: String::from("alice"),
+                duration,
+                secret: format!("secret{}", index),
+                resolver: None,
+                address: None,
+                reverse_record: false,
+            };
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::MakeCommitment {
+                    name: registration.name.clone(),
+                    owner: registration.owner.clone(),
+                    secret: registration.secret.clone(),
+                    resolver: None,
+                    address: None,
+                },
+            )
+            .unwrap();
+            let commitment_response: MakeCommitmentResponse = from_binary(&res).unwrap();
+            let msg = ExecuteMsg::Commit {
+                commitment: commitment_response.commitment,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+            registrations.push(registration);
+        }
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+        let funds = coins(12_000_000, "usei");
+        // The duplicated name fails the whole batch
+        let msg = ExecuteMsg::BatchRegister {
+            registrations: registrations.clone(),
+            skip_unavailable: false,
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &funds), msg).unwrap_err();
+        assert_eq!(err, ContractError::UnavailabledName {});
+
+        let msg = ExecuteMsg::BatchRegister {
+            registrations,
+            skip_unavailable: true,
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &funds), msg).unwrap();
+        // Two registrations of 4 messages each and the refund
+        assert_eq!(res.messages.len(), 9);
+        assert_eq!(
+            res.messages[8].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("alice"),
+                amount: coins(2_000_000, "usei"),
+            })
+        );
+        assert!(res.attributes.contains(&Attribute::new("name", "carol")));
+        assert!(res.attributes.contains(&Attribute::new("skipped", "alice")));
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("total", "10000000")));
+
+        let renewals = vec![
+            RenewRequest {
+                name: String::from("alice"),
+                duration,
+            },
+            RenewRequest {
+                name: String::from("carol"),
+                duration,
+            },
+        ];
+        let msg = ExecuteMsg::BatchRenew {
+            renewals: renewals.clone(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &coins(5_000_000, "usei")),
+            msg,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientFund {
+                amount: Uint128::from(5_000_000u128),
+                required: Uint128::from(10_000_000u128)
+            }
+        );
+        let funds = coins(10_000_000, "usei");
+
+        // A name can only appear once per batch
+        let mut duplicated = renewals.clone();
+        duplicated[1].name = String::from("Alice");
+        let msg = ExecuteMsg::BatchRenew {
+            renewals: duplicated,
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &funds), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BadRequest {
+                msg: String::from("alice is renewed more than once")
+            }
+        );
+
+        let msg = ExecuteMsg::BatchRenew { renewals };
+        let res = execute(deps.as_mut(), env, mock_info("alice", &funds), msg).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert!(res.attributes.contains(&Attribute::new("name", "alice")));
+        assert!(res.attributes.contains(&Attribute::new("cost", "5000000")));
+    }
+
+    #[test]
+    fn test_auto_renew() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let auto_renew_config = AutoRenewConfig {
+            renew_window: 30 * 24 * 3600,
+            keeper_tip_bps: 100,
+        };
+        let msg = ExecuteMsg::SetAutoRenewConfig {
+            auto_renew_config: auto_renew_config.clone(),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetAutoRenewConfig {}).unwrap();
+        let res: AutoRenewConfigResponse = from_binary(&res).unwrap();
+        assert_eq!(res.auto_renew_config, auto_renew_config);
+
+        // Every name is owned by "referrer" in the mock registry, only the owner can deposit
+        let funds = coins(12_000_000, "usei");
+        let msg = ExecuteMsg::DepositAutoRenew {
+            name: String::from("alice"),
+            duration: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &funds),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Unauthorized {
+                description: Some(String::from("alice is not owned by alice"))
+            }
+        );
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("referrer", &funds),
+            msg,
+        )
+        .unwrap();
+
+        // The name is at the end of its grace period, so it is renewed from the escrow
+        let msg = ExecuteMsg::ProcessRenewals { limit: None };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("keeper", &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("keeper"),
+                amount: coins(50_000, "usei"),
+            })
+        );
+        assert!(res.attributes.contains(&Attribute::new("renewed", "alice")));
+
+        let msg = QueryMsg::AutoRenew {
+            name: String::from("alice"),
+        };
+        let res: AutoRenewResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(
+            res.auto_renew,
+            Some(AutoRenew {
+                balance: Coin::new(6_950_000, "usei"),
+                duration: 24 * 3600 * 365,
+                owner: Addr::unchecked("referrer"),
+            })
+        );
+
+        let msg = ExecuteMsg::WithdrawAutoRenew {
+            name: String::from("alice"),
+            amount: None,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        let res = execute(deps.as_mut(), mock_env(), mock_info("referrer", &[]), msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("referrer"),
+                amount: coins(6_950_000, "usei"),
+            })
+        );
+        let msg = QueryMsg::AutoRenew {
+            name: String::from("alice"),
+        };
+        let res: AutoRenewResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.auto_renew, None);
+
+        // The escrow of a name that changed owner is refunded instead of renewing it
+        AUTO_RENEWALS
+            .save(
+                deps.as_mut().storage,
+                String::from("bobby"),
+                &AutoRenew {
+                    balance: Coin::new(12_000_000, "usei"),
+                    duration: 24 * 3600 * 365,
+                    owner: Addr::unchecked("alice"),
+                },
+            )
+            .unwrap();
+        AUTO_RENEW_ESCROW
+            .save(
+                deps.as_mut().storage,
+                String::from("usei"),
+                &Uint128::from(12_000_000u128),
+            )
+            .unwrap();
+        let msg = ExecuteMsg::ProcessRenewals { limit: None };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("keeper", &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("alice"),
+                amount: coins(12_000_000, "usei"),
+            })
+        );
+        assert!(!AUTO_RENEWALS.has(deps.as_ref().storage, String::from("bobby")));
+
+        // So is the escrow of a name reserved after the deposit
+        let msg = ExecuteMsg::DepositAutoRenew {
+            name: String::from("carol"),
+            duration: None,
+        };
+        let funds = coins(12_000_000, "usei");
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("referrer", &funds),
+            msg,
+        )
+        .unwrap();
+        let msg = ExecuteMsg::SetPremiumName {
+            name: String::from("carol"),
+            premium: PremiumName::Reserved {},
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        let msg = ExecuteMsg::ProcessRenewals { limit: None };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("keeper", &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("referrer"),
+                amount: funds,
+            })
+        );
+        assert!(res.attributes.contains(&Attribute::new(
+            "skipped",
+            "carol: ReservedName: carol can only be registered by the owner."
+        )));
+        assert!(!AUTO_RENEWALS.has(deps.as_ref().storage, String::from("carol")));
+    }
+
+    #[test]
+    fn test_duration_limits() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let year: u64 = 24 * 3600 * 365;
+        let msg = ExecuteMsg::SetDurationLimits {
+            max_registration_duration: Some(2 * year),
+            max_expiry_duration: Some(year),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BadRequest {
+                msg: String::from(
+                    "max_expiry_duration must not be less than max_registration_duration"
+                )
+            }
+        );
+        let msg = ExecuteMsg::SetDurationLimits {
+            max_registration_duration: Some(2 * year),
+            max_expiry_duration: Some(2 * year),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::DurationLimits {}).unwrap();
+        let res: DurationLimitsResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            res,
+            DurationLimitsResponse {
+                min_registration_duration: year,
+                max_registration_duration: Some(2 * year),
+                max_expiry_duration: Some(2 * year),
+            }
+        );
+
+        let cost = get_cost(deps.as_ref(), &mock_env(), String::from("alice"), 3 * year);
+        assert_eq!(
+            cost.unwrap_err(),
+            ContractError::DurationTooLong {
+                input_duration: 3 * year,
+                max_duration: 2 * year
+            }
+        );
+
+        // The name expires in 335 days, renewing it for 2 years exceeds the cap
+        let mut env = mock_env();
+        env.block.time = env.block.time.minus_seconds(year);
+        let msg = ExecuteMsg::OwnerRenew {
+            name: String::from("alice"),
+            duration: 2 * year,
+        };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        let expires = mock_env().block.time.seconds() - 2_592_000 + 2 * year;
+        assert_eq!(
+            err,
+            ContractError::ExpiryTooFar {
+                expires,
+                max_expires: env.block.time.seconds() + 2 * year,
+            }
+        );
+        let msg = ExecuteMsg::OwnerRenew {
+            name: String::from("alice"),
+            duration: year,
+        };
+        execute(deps.as_mut(), env, info, msg).unwrap();
+
+        // Auto-renewals past the cap are skipped and the others still processed
+        let funds = coins(20_000_000, "usei");
+        for (name, duration) in [("alice", 2 * year), ("bobby", year)] {
+            let msg = ExecuteMsg::DepositAutoRenew {
+                name: String::from(name),
+                duration: Some(duration),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("referrer", &funds), msg).unwrap();
+        }
+        let mut env = mock_env();
+        env.block.time = env.block.time.minus_seconds(40 * 24 * 3600);
+        let msg = ExecuteMsg::ProcessRenewals { limit: None };
+        let res = execute(deps.as_mut(), env, mock_info("keeper", &[]), msg).unwrap();
+        assert!(res.attributes.contains(&Attribute::new("renewed", "bobby")));
+        assert!(!res.attributes.contains(&Attribute::new("renewed", "alice")));
+
+        // The escrow of the capped name is reported and refunded
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "skipped" && attr.value.starts_with("alice: ExpiryTooFar")));
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("referrer"),
+                amount: funds,
+            })
+        );
+        assert!(!AUTO_RENEWALS.has(deps.as_ref().storage, String::from("alice")));
+    }
+
+    #[test]
+    fn test_duration_discounts() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let year: u64 = 24 * 3600 * 365;
+        let msg = ExecuteMsg::SetDurationDiscounts {
+            duration_discounts: vec![DurationDiscount {
+                min_duration: 2 * year,
+                percentage: 100,
+            }],
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidDurationDiscount {
+                description: String::from("Percentage must be in 1~99")
+            }
+        );
+        let msg = ExecuteMsg::SetDurationDiscounts {
+            duration_discounts: vec![
+                DurationDiscount {
+                    min_duration: 5 * year,
+                    percentage: 15,
+                },
+                DurationDiscount {
+                    min_duration: 2 * year,
+                    percentage: 5,
+                },
+            ],
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::DurationDiscounts {}).unwrap();
+        let res: DurationDiscountsResponse = from_binary(&res).unwrap();
+        assert_eq!(res.duration_discounts[0].min_duration, 2 * year);
+        assert_eq!(res.duration_discounts[1].min_duration, 5 * year);
+
+        let cost = get_cost(deps.as_ref(), &mock_env(), String::from("alice"), year).unwrap();
+        assert_eq!(cost, Uint128::from(5_000_000u128));
+        let cost = get_cost(deps.as_ref(), &mock_env(), String::from("alice"), 3 * year).unwrap();
+        assert_eq!(cost, Uint128::from(14_250_000u128));
+
+        let msg = QueryMsg::RentPrice {
+            name: String::from("alice"),
+            duration: 5 * year,
+            promo_code: None,
+        };
+        let res: RentPriceResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.base_price, Uint128::from(25_000_000u128));
+        assert_eq!(res.duration_discount, Uint128::from(3_750_000u128));
+        assert_eq!(res.premium, Uint128::zero());
+        assert_eq!(res.price, Uint128::from(21_250_000u128));
+    }
+
+    #[test]
+    fn test_rent_price_breakdown() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let duration: u64 = 24 * 3600 * 365;
+        let msg = QueryMsg::RentPrice {
+            name: String::from("alice"),
+            duration,
+            promo_code: None,
+        };
+        let res: RentPriceResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(
+            res,
+            RentPriceResponse {
+                price: Uint128::from(5_000_000u128),
+                denom: String::from("usei"),
+                duration,
+                annual_price: 5_000_000,
+                exchange_rate: Some(Decimal::one()),
+                base_price: Uint128::from(5_000_000u128),
+                duration_discount: Uint128::zero(),
+                premium: Uint128::zero(),
+                discount: Uint128::zero(),
+                quotes: coins(5_000_000, "usei"),
+            }
+        );
+
+        // Failures are explained instead of reported as a bare error
+        let msg = QueryMsg::RentPrice {
+            name: String::from("ab"),
+            duration,
+            promo_code: None,
+        };
+        let err = query(deps.as_ref(), mock_env(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err(
+                "NameTooShort: The name is 2 characters long, but the minimum is 3."
+            )
+        );
+        let msg = QueryMsg::RentPrice {
+            name: String::from("alice"),
+            duration: 3600,
+            promo_code: None,
+        };
+        let err = query(deps.as_ref(), mock_env(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err(format!(
+                "DurationTooShort: The duration is 3600, but the minimum is {}.",
+                duration
+            ))
+        );
+    }
+
+    #[test]
+    fn test_unicode_names() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let cases = [
+            ("café", true),
+            ("日本語", true),
+            ("🏳\u{200d}🌈🌈🌈", true),
+            // Only the canonical form is accepted
+            ("Alice", false),
+            ("cafe\u{301}", false),
+            ("🏳\u{fe0f}\u{200d}🌈🌈🌈", false),
+            // Cyrillic "а" in a Latin name
+            ("pаypal", false),
+            ("al ice", false),
+        ];
+        for (name, is_valid_name) in cases {
+            let msg = QueryMsg::IsValidName {
+                name: String::from(name),
+            };
+            let res: IsValidNameResponse =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(res.is_valid_name, is_valid_name, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_name_policy() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let failed_rule = |deps: Deps<SeiQueryWrapper>, name: &str| {
+            let msg = QueryMsg::IsValidName {
+                name: String::from(name),
+            };
+            from_binary::<IsValidNameResponse>(&query(deps, mock_env(), msg).unwrap())
+                .unwrap()
+                .failed_rule
+        };
+        assert_eq!(failed_rule(deps.as_ref(), "alice--bob"), None);
+        assert_eq!(
+            failed_rule(deps.as_ref(), "alice-"),
+            Some(NameRule::TrailingHyphen {})
+        );
+        assert_eq!(
+            failed_rule(deps.as_ref(), "-alice"),
+            Some(NameRule::LeadingHyphen {})
+        );
+        assert_eq!(
+            failed_rule(deps.as_ref(), "ab"),
+            Some(NameRule::MinLength { min_length: 3 })
+        );
+        assert_eq!(
+            failed_rule(deps.as_ref(), ""),
+            Some(NameRule::Normalization {
+                reason: String::from("EmptyLabel: Names cannot contain empty labels.")
+            })
+        );
+        assert_eq!(
+            failed_rule(deps.as_ref(), "alice.bob"),
+            Some(NameRule::Normalization {
+                reason: String::from("DisallowedCharacter: '.' is not allowed in names.")
+            })
+        );
+
+        let name_policy = NamePolicy {
+            min_length: 4,
+            max_length: Some(8),
+            allowed_characters: vec![
+                CharacterClass::Digit {},
+                CharacterClass::Latin {},
+                CharacterClass::Hyphen {},
+            ],
+            allow_leading_hyphen: false,
+            allow_trailing_hyphen: false,
+            allow_consecutive_hyphens: false,
+            blocked_substrings: vec![String::from("admin")],
+        };
+        let msg = ExecuteMsg::SetNamePolicy {
+            name_policy: name_policy.clone(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NotOwner { .. }));
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let res: NamePolicyResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::NamePolicy {}).unwrap())
+                .unwrap();
+        assert_eq!(res.name_policy, name_policy);
+
+        assert_eq!(failed_rule(deps.as_ref(), "alice"), None);
+        assert_eq!(
+            failed_rule(deps.as_ref(), "bob"),
+            Some(NameRule::MinLength { min_length: 4 })
+        );
+        assert_eq!(
+            failed_rule(deps.as_ref(), "alicealice"),
+            Some(NameRule::MaxLength { max_length: 8 })
+        );
+        assert_eq!(
+            failed_rule(deps.as_ref(), "café"),
+            Some(NameRule::DisallowedCharacter {
+                character: String::from("é")
+            })
+        );
+        assert_eq!(
+            failed_rule(deps.as_ref(), "ali--ce"),
+            Some(NameRule::ConsecutiveHyphens {})
+        );
+        assert_eq!(
+            failed_rule(deps.as_ref(), "myadmin"),
+            Some(NameRule::BlockedSubstring {
+                substring: String::from("admin")
+            })
+        );
+
+        let msg = ExecuteMsg::SetNamePolicy {
+            name_policy: NamePolicy {
+                blocked_substrings: vec![String::from("Admin")],
+                ..name_policy
+            },
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidNamePolicy {
+                description: String::from("Blocked substring Admin is not canonical")
+            }
+        );
+    }
+
+    #[test]
+    fn test_blocklist() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            registrar_address: String::from("registrar_address"),
+            reverse_registrar_address: String::from("reverse_registrar_address"),
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            min_registration_duration: 24 * 3600 * 365,
+            max_registration_duration: None,
+            max_expiry_duration: None,
+            duration_discounts: vec![],
+            name_policy: None,
+            price_tiers: price_tiers(),
+            whitelist_price: 640_000_000u64,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+            accepted_denoms: vec![],
+            expiry_premium: None,
+            fee_split: None,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            description: "".to_string(),
+            treasury: None,
+        };
+        let info = mock_info("creator", &coins(0, "usei"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let bob_hash = hex::encode(keccak256("bob".as_bytes()));
+        let msg = ExecuteMsg::AddToBlocklist {
+            entries: vec![
+                BlocklistEntry {
+                    entry: String::from("alice"),
+                    kind: BlockKind::Label {},
+                },
+                BlocklistEntry {
+                    entry: bob_hash.clone(),
+                    kind: BlockKind::LabelHash {},
+                },
+                BlocklistEntry {
+                    entry: String::from("scam"),
+                    kind: BlockKind::Substring {},
+                },
+            ],
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NotOwner { .. }));
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::AddToBlocklist {
+            entries: vec![BlocklistEntry {
+                entry: String::from("Scam"),
+                kind: BlockKind::Substring {},
+            }],
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidBlocklistEntry {
+                description: String::from("Scam is not canonical")
+            }
+        );
+
+        // Exact labels are only listed by hash
+        let alice_hash = hex::encode(keccak256("alice".as_bytes()));
+        let msg = QueryMsg::Blocklist {
+            start_after: None,
+            limit: Some(2),
+        };
+        let res: BlocklistResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(
+            res.entries,
+            vec![
+                BlocklistEntry {
+                    entry: String::from("scam"),
+                    kind: BlockKind::Substring {},
+                },
+                BlocklistEntry {
+                    entry: bob_hash.clone(),
+                    kind: BlockKind::LabelHash {},
+                },
+            ]
+        );
+        let msg = QueryMsg::Blocklist {
+            start_after: res.entries.last().cloned(),
+            limit: None,
+        };
+        let res: BlocklistResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(
+            res.entries,
+            vec![BlocklistEntry {
+                entry: alice_hash,
+                kind: BlockKind::LabelHash {},
+            }]
+        );
+
+        let duration: u64 = 24 * 3600 * 365;
+        for name in ["alice", "bob", "scammer"] {
+            let msg = ExecuteMsg::Register {
+                name: String::from(name),
+                owner: String::from("alice"),
+                duration,
+                secret: String::from("secret"),
+                resolver: None,
+                address: None,
+                reverse_record: false,
+                promo_code: None,
+            };
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("alice", &coins(5_000_000, "usei")),
+                msg,
+            )
+            .unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::BlockedName {
+                    name: String::from(name)
+                }
+            );
+        }
+
+        // The owner can still register blocked names
+        let msg = ExecuteMsg::OwnerRegister {
+            name: String::from("alice"),
+            owner: String::from("alice"),
+            duration,
+            resolver: None,
+            address: None,
+            reverse_record: false,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::RemoveFromBlocklist {
+            entries: vec![BlocklistEntry {
+                entry: String::from("bob"),
+                kind: BlockKind::Label {},
+            }],
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let msg = QueryMsg::Blocklist {
+            start_after: None,
+            limit: None,
+        };
+        let res: BlocklistResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.entries.len(), 2);
+    }
+
+    #[test]
+    fn test_migrate_legacy_config() {
+        let mut deps = mock_dependencies(&[]);
+        let legacy_config = LegacyConfig {
+            min_registration_duration: 24 * 3600 * 365,
+            tier1_price: 640_000_000u64,
+            tier2_price: 160_000_000u64,
+            tier3_price: 5_000_000u64,
+            whitelist_price: 640_000_000u64,
+            referal_percentage: (20, 40),
+            enable_registration: true,
+            registrar_address: deps.api.addr_canonicalize("registrar_address").unwrap(),
+            reverse_registrar_address: deps
+                .api
+                .addr_canonicalize("reverse_registrar_address")
+                .unwrap(),
+            owner: deps.api.addr_canonicalize("creator").unwrap(),
+            description: "".to_string(),
+        };
+        LEGACY_CONFIG
+            .save(deps.as_mut().storage, &legacy_config)
+            .unwrap();
+        LEGACY_WHITELIST
+            .save(
+                deps.as_mut().storage,
+                String::from("bob"),
+                &(vec![1u8, 2u8], 40),
+            )
+            .unwrap();
+        let label = get_label_from_name(&String::from("unknown"));
+        let unknown_node = keccak256(&[&hex::decode(BASE_NODE).unwrap(), &label[..]].concat());
+        LEGACY_WHITELIST
+            .save(
+                deps.as_mut().storage,
+                String::from("unknown"),
+                &(unknown_node, 40),
+            )
+            .unwrap();
+        let msg = MigrateMsg {
+            max_commitment_age: 86400,
+            min_commitment_age: 60,
+            price_source: PriceSource::SeiOracle {
+                max_staleness_blocks: 100,
+            },
+        };
+        migrate(deps.as_mut(), mock_env(), msg.clone()).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PriceTiers {}).unwrap();
+        let res: PriceTiersResponse = from_binary(&res).unwrap();
+        assert_eq!(res.tiers, price_tiers());
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Treasury {}).unwrap();
+        let res: TreasuryResponse = from_binary(&res).unwrap();
+        assert_eq!(res.treasury, "creator");
+        let cost = get_cost(
+            deps.as_ref(),
+            &mock_env(),
+            String::from("alice"),
+            24 * 3600 * 365,
+        )
+        .unwrap();
+        assert_eq!(cost, Uint128::from(5_000_000u128));
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::WhiteList {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let res: WhiteListResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            res.entries,
+            vec![WhiteListInfo {
+                name: String::from("bob"),
+                node: vec![1u8, 2u8],
+                owner: Addr::unchecked("referrer"),
+                percentage: 40,
+                expires: None,
+            }]
+        );
+
+        // Migrating again leaves the config as is
+        let res = migrate(deps.as_mut(), mock_env(), msg).unwrap();
+        assert_eq!(res.attributes.len(), 1);
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PriceTiers {}).unwrap();
+        let res: PriceTiersResponse = from_binary(&res).unwrap();
+        assert_eq!(res.tiers, price_tiers());
+    }
 }
@@ -24,6 +24,9 @@ pub enum ContractError {
     #[error("Not available")]
     NotAvailable {},
 
+    #[error("ExpiryOverflow: The duration {duration} pushes the expiry out of range.")]
+    ExpiryOverflow { duration: u64 },
+
     #[error("Burn burn: {msg}")]
     Burned { msg: String },
 
@@ -149,12 +149,12 @@ where
         let token = TokenInfo::<T> {
             owner: deps.api.addr_validate(&owner)?,
             approvals: vec![],
-            name: name,
+            name,
             description: description.unwrap_or_default(),
             // image: image,
-            extension: extension,
-            token_uri: token_uri,
-            reward_claimed: reward_claimed,
+            extension,
+            token_uri,
+            reward_claimed,
         };
         self.tokens
             .update(deps.storage, &token_id, |old| match old {
@@ -187,7 +187,7 @@ where
 
         let config = CONFIG.load(deps.storage)?;
         // let timestamp = env.block.time.seconds();
-        return self._mint(
+        self._mint(
             deps,
             env,
             info,
@@ -198,7 +198,7 @@ where
             0,
             msg.extension,
             msg.token_id,
-        );
+        )
     }
 }
 
@@ -368,13 +368,13 @@ where
         recipient: &str,
         token_id: &str,
     ) -> Result<TokenInfo<T>, ContractError> {
-        let mut token = self.tokens.load(deps.storage, &token_id)?;
+        let mut token = self.tokens.load(deps.storage, token_id)?;
         // ensure we have permissions
         self.check_can_send(deps.as_ref(), env, info, &token)?;
         // set owner and remove existing approvals
         token.owner = deps.api.addr_validate(recipient)?;
         token.approvals = vec![];
-        self.tokens.save(deps.storage, &token_id, &token)?;
+        self.tokens.save(deps.storage, token_id, &token)?;
         Ok(token)
     }
 
@@ -390,17 +390,13 @@ where
         add: bool,
         expires: Option<Expiration>,
     ) -> Result<TokenInfo<T>, ContractError> {
-        let mut token = self.tokens.load(deps.storage, &token_id)?;
+        let mut token = self.tokens.load(deps.storage, token_id)?;
         // ensure we have permissions
         self.check_can_approve(deps.as_ref(), env, info, &token)?;
 
         // update the approval list (remove any for the same spender before adding)
         let spender_addr = deps.api.addr_validate(spender)?;
-        token.approvals = token
-            .approvals
-            .into_iter()
-            .filter(|apr| apr.spender != spender_addr)
-            .collect();
+        token.approvals.retain(|apr| apr.spender != spender_addr);
 
         // only difference between approve and revoke
         if add {
@@ -416,7 +412,7 @@ where
             token.approvals.push(approval);
         }
 
-        self.tokens.save(deps.storage, &token_id, &token)?;
+        self.tokens.save(deps.storage, token_id, &token)?;
 
         Ok(token)
     }
@@ -73,7 +73,12 @@ where
         let config = CONFIG.load(deps.storage)?;
         let registry_address = deps.api.addr_humanize(&config.registry_address)?;
 
-        let expire = env.block.time.seconds() + duration;
+        let expire = env
+            .block
+            .time
+            .seconds()
+            .checked_add(duration)
+            .ok_or(ContractError::ExpiryOverflow { duration })?;
         EXPIRIES.save(deps.storage, id.clone(), &expire)?;
         let token = self.tokens.may_load(deps.storage, &id)?;
         if let Some(_token) = token {
@@ -143,7 +148,9 @@ where
         if expiry + config.grace_period < env.block.time.seconds() {
             return Err(ContractError::Expired {});
         }
-        let new_expiry = expiry + duration;
+        let new_expiry = expiry
+            .checked_add(duration)
+            .ok_or(ContractError::ExpiryOverflow { duration })?;
         EXPIRIES.save(deps.storage, id.clone(), &new_expiry)?;
         Ok(Response::new()
             .add_attribute("method", "renew")
@@ -1,3 +1,5 @@
+#![allow(clippy::too_many_arguments)]
+
 mod error;
 pub mod execute;
 pub mod handler;
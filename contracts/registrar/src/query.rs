@@ -50,7 +50,7 @@ where
         }
         Ok(NftInfoResponse {
             extension: info.extension,
-            token_uri: token_uri,
+            token_uri,
         })
     }
 
@@ -255,7 +255,7 @@ where
             },
             info: NftInfoResponse {
                 extension: info.extension,
-                token_uri: token_uri,
+                token_uri,
             },
         })
     }
@@ -318,8 +318,8 @@ where
         let registry_address = deps.api.addr_humanize(&config.registry_address)?;
         Ok(ConfigResponse {
             grace_period: config.grace_period,
-            registry_address: registry_address,
-            owner: owner,
+            registry_address,
+            owner,
             base_node: config.base_node,
             base_name: config.base_name,
         })
@@ -6,7 +6,6 @@ use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 use cosmwasm_std::{
     coins, from_binary, to_binary, Addr, CosmosMsg, DepsMut, Empty, Response, WasmMsg,
 };
-use cw721;
 use cw721::{ContractInfoResponse, Cw721Query, Cw721ReceiveMsg, NftInfoResponse, OwnerOfResponse};
 use dotlabs::registrar::{
     ConfigResponse, ExecuteMsg, Extension, InstantiateMsg, IsAvailableResponse, MintMsg, QueryMsg,
@@ -523,7 +522,7 @@ fn test_is_available() {
     let res = entry::query(deps.as_ref(), mock_env(), QueryMsg::IsAvailable { id }).unwrap();
 
     let value: IsAvailableResponse = from_binary(&res).unwrap();
-    assert_eq!(value.available, true);
+    assert!(value.available);
 }
 
 #[test]
@@ -563,10 +562,7 @@ fn test_register() {
             description: "".to_string(),
         },
     };
-    assert_eq!(
-        entry::execute(deps.as_mut(), mock_env(), info, msg).is_err(),
-        true
-    );
+    assert!(entry::execute(deps.as_mut(), mock_env(), info, msg).is_err());
 
     let info = mock_info("controller_address", &coins(0, "uusd"));
     let msg = ExecuteMsg::Register {
@@ -695,10 +691,7 @@ fn test_reclaim() {
         id: id.clone(),
         owner: "bob".to_string(),
     };
-    assert_eq!(
-        entry::execute(deps.as_mut(), mock_env(), info, msg).is_err(),
-        true
-    );
+    assert!(entry::execute(deps.as_mut(), mock_env(), info, msg).is_err());
 
     // Reclaim alice.ust from controller should error
     let info = mock_info("controller", &coins(0, "uusd"));
@@ -706,10 +699,7 @@ fn test_reclaim() {
         id: id.clone(),
         owner: "controller".to_string(),
     };
-    assert_eq!(
-        entry::execute(deps.as_mut(), mock_env(), info, msg).is_err(),
-        true
-    );
+    assert!(entry::execute(deps.as_mut(), mock_env(), info, msg).is_err());
 
     // Transfer NFT from alice to bob
     let info = mock_info("alice", &coins(0, "uusd"));
@@ -738,10 +728,7 @@ fn test_reclaim() {
         id: id.clone(),
         owner: "controller".to_string(),
     };
-    assert_eq!(
-        entry::execute(deps.as_mut(), mock_env(), info, msg).is_err(),
-        true
-    );
+    assert!(entry::execute(deps.as_mut(), mock_env(), info, msg).is_err());
 
     // Reclaim alice.ust from bob
     let info = mock_info("bob", &coins(0, "uusd"));
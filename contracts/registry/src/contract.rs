@@ -1,9 +1,9 @@
 use crate::error::ContractError;
 use crate::handler::{
-    get_config, is_node_owner, query_is_approved_for_all, query_record, query_record_by_node,
-    query_record_owner_by_node, query_record_resolver_by_node, query_record_ttl_by_node,
-    set_approval_for_all, set_config, set_owner, set_record, set_resolver, set_subnode_owner,
-    set_subnode_record, set_ttl,
+    get_config, is_node_owner, query_is_approved_for_all, query_record, query_record_by_dns_name,
+    query_record_by_node, query_record_owner_by_node, query_record_resolver_by_node,
+    query_record_ttl_by_node, set_approval_for_all, set_config, set_owner, set_record,
+    set_resolver, set_subnode_owner, set_subnode_record, set_ttl,
 };
 use crate::state::{Config, Record, CONFIG, RECORDS};
 #[cfg(not(feature = "library"))]
@@ -84,6 +84,9 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetRecord { name } => to_binary(&query_record(deps, env, name)?),
         QueryMsg::GetRecordByNode { node } => to_binary(&query_record_by_node(deps, env, node)?),
+        QueryMsg::GetRecordByDnsName { name } => {
+            to_binary(&query_record_by_dns_name(deps, env, name)?)
+        }
         QueryMsg::GetIsNodeOwner { node, address } => {
             to_binary(&is_node_owner(deps, env, node, address)?)
         }
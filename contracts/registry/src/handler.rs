@@ -1,12 +1,13 @@
 use crate::error::ContractError;
 use crate::state::{Record, CONFIG, OPERATORS, RECORDS};
-use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
 use dotlabs::registry::{
     ConfigResponse, GetRecordOwnerResponse, GetRecordResolverResponse, GetRecordTtlResponse,
     OperatorResponse, RecordResponse,
 };
 use dotlabs::utils::keccak256;
 use dotlabs::utils::namehash;
+use dotlabs::utils::namehash_from_dns;
 
 fn only_owner(deps: Deps, info: &MessageInfo) -> Result<bool, ContractError> {
     let config = CONFIG.load(deps.storage)?;
@@ -23,7 +24,7 @@ fn only_owner(deps: Deps, info: &MessageInfo) -> Result<bool, ContractError> {
 fn only_authorized(
     deps: &DepsMut,
     info: &MessageInfo,
-    node: &Vec<u8>,
+    node: &[u8],
 ) -> Result<bool, ContractError> {
     let record_option = RECORDS.may_load(deps.storage, node.to_vec())?;
     let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
@@ -42,10 +43,10 @@ fn only_authorized(
             }
         }
     }
-    return Err(ContractError::NotNodeOwner {
+    Err(ContractError::NotNodeOwner {
         sender: info.sender.to_string(),
-        node: format!("{:?}", node.clone()),
-    });
+        node: format!("{:?}", node),
+    })
 }
 
 pub fn set_subnode_record(
@@ -129,7 +130,7 @@ fn _set_owner(
             ttl: 0,
         },
     )?;
-    return Ok(Response::default());
+    Ok(Response::default())
 }
 
 pub fn set_record(
@@ -221,7 +222,7 @@ pub fn is_node_owner(deps: Deps, _env: Env, node: Vec<u8>, address: String) -> S
             return Ok(operator);
         }
     }
-    return Ok(false);
+    Ok(false)
 }
 
 pub fn query_record_by_node(deps: Deps, _env: Env, node: Vec<u8>) -> StdResult<RecordResponse> {
@@ -270,6 +271,12 @@ pub fn query_record_ttl_by_node(
     Ok(GetRecordTtlResponse { ttl })
 }
 
+pub fn query_record_by_dns_name(deps: Deps, env: Env, name: Binary) -> StdResult<RecordResponse> {
+    let node =
+        namehash_from_dns(name.as_slice()).map_err(|err| StdError::generic_err(err.to_string()))?;
+    query_record_by_node(deps, env, node)
+}
+
 pub fn query_record(deps: Deps, _env: Env, name: String) -> StdResult<RecordResponse> {
     let node = namehash(name.as_str());
     let record = RECORDS.load(deps.storage, node)?;
@@ -322,7 +329,7 @@ pub fn query_is_approved_for_all(
         }
     }
 
-    return Ok(OperatorResponse { is_approve: false });
+    Ok(OperatorResponse { is_approve: false })
 }
 
 pub fn set_config(
@@ -2,12 +2,15 @@ mod tests {
     use crate::contract::{execute, instantiate, query};
     use crate::error::ContractError;
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_binary, Addr};
+    use cosmwasm_std::{coins, from_binary, Addr, Binary, StdError};
     use dotlabs::registry::{
         ConfigResponse, ExecuteMsg, InstantiateMsg, OperatorResponse, QueryMsg, RecordResponse,
     };
+    use dotlabs::normalize::{normalize, NormalizeError};
     use dotlabs::utils::{
-        convert_namehash_to_hex_string, get_label_from_name, keccak256, namehash,
+        convert_namehash_to_hex_string, dns_decode, dns_encode, get_label_from_name, keccak256,
+        namehash, namehash_from_dns, punycode_decode, punycode_encode, to_ascii, to_unicode,
+        NameError,
     };
 
     #[test]
@@ -47,6 +50,232 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_normalize() {
+        // Case folding and NFC
+        assert_eq!(normalize("Alice.UST").unwrap(), "alice.ust");
+        assert_eq!(normalize("cafe\u{301}").unwrap(), "caf\u{e9}");
+        assert_eq!(normalize("_alice").unwrap(), "_alice");
+        assert_eq!(normalize("日本ひらがな").unwrap(), "日本ひらがな");
+
+        // Emoji sequences, without presentation selectors
+        assert_eq!(normalize("👍🏽").unwrap(), "👍🏽");
+        assert_eq!(normalize("🏳\u{fe0f}\u{200d}🌈").unwrap(), "🏳\u{200d}🌈");
+        assert_eq!(normalize("1\u{fe0f}\u{20e3}").unwrap(), "1\u{20e3}");
+        assert_eq!(normalize("⌚⬛").unwrap(), "⌚⬛");
+        // Text-default emoji are kept inside sequences only
+        assert_eq!(normalize("❤\u{fe0f}\u{200d}🔥").unwrap(), "❤\u{200d}🔥");
+        assert_eq!(normalize("✌🏽").unwrap(), "✌🏽");
+        // Other symbols and lone text-default emoji are rejected, even with a selector
+        for symbol in ['⌘', '⬀', '✦', '☃'] {
+            assert_eq!(
+                normalize(&format!("{}\u{fe0f}", symbol)),
+                Err(NormalizeError::DisallowedCharacter { character: symbol })
+            );
+        }
+
+        assert_eq!(normalize("a..ust"), Err(NormalizeError::EmptyLabel {}));
+        assert_eq!(
+            normalize("al ice"),
+            Err(NormalizeError::DisallowedCharacter { character: ' ' })
+        );
+        assert_eq!(
+            normalize("ａｌｉｃｅ"),
+            Err(NormalizeError::DisallowedCharacter { character: 'ａ' })
+        );
+        assert_eq!(
+            normalize("al_ice"),
+            Err(NormalizeError::InvalidUnderscore {})
+        );
+        assert_eq!(
+            normalize("xn--alice"),
+            Err(NormalizeError::InvalidLabelExtension {})
+        );
+        assert_eq!(
+            normalize("\u{301}alice"),
+            Err(NormalizeError::LeadingCombiningMark {
+                character: '\u{301}'
+            })
+        );
+        assert_eq!(
+            normalize("al\u{200d}ice"),
+            Err(NormalizeError::InvalidEmojiSequence {
+                character: '\u{200d}'
+            })
+        );
+        // Latin with a Cyrillic "а"
+        assert_eq!(
+            normalize("pаypal"),
+            Err(NormalizeError::MixedScript {
+                first: "Latin".to_string(),
+                second: "Cyrillic".to_string(),
+            })
+        );
+        // All Cyrillic, but reads as "apple"
+        assert_eq!(
+            normalize("аррӏе"),
+            Err(NormalizeError::Confusable {
+                label: "аррӏе".to_string()
+            })
+        );
+
+        // Every contract hashes the canonical form
+        assert_eq!(namehash("Alice.UST"), namehash("alice.ust"));
+        assert_eq!(
+            get_label_from_name(&String::from("ALICE")),
+            get_label_from_name(&String::from("alice"))
+        );
+        // A label that can't be normalized is hashed as given, the others stay canonical
+        assert_eq!(
+            get_label_from_name(&String::from("Al ice")),
+            keccak256("Al ice".as_bytes())
+        );
+        assert_eq!(namehash("Al ice.UST"), namehash("Al ice.ust"));
+        assert_ne!(namehash("Al ice.ust"), namehash("al ice.ust"));
+    }
+
+    // Samples from RFC 3492 section 7.1
+    #[test]
+    fn test_punycode() {
+        let samples = [
+            // (A) Arabic (Egyptian)
+            ("ليهمابتكلموشعربي؟", "egbpdaj6bu4bxfgehfvwxn"),
+            // (B) Chinese (simplified)
+            ("他们为什么不说中文", "ihqwcrb4cv8a8dqg056pqjye"),
+            // (I) Russian (Cyrillic)
+            (
+                "почемужеонинеговорятпорусски",
+                "b1abfaaepdrnnbgefbadotcwatmq2g4l",
+            ),
+            // (L) 3<nen>B<gumi><kinpachi><sensei>, lowercased
+            ("3年b組金八先生", "3b-ww4c5e180e575a65lsy2b"),
+            // (S) -> $1.00 <-
+            ("-> $1.00 <-", "-> $1.00 <--"),
+        ];
+        for (decoded, encoded) in samples {
+            assert_eq!(punycode_encode(decoded).unwrap(), encoded);
+            assert_eq!(punycode_decode(encoded).unwrap(), decoded);
+        }
+        // Mixed case annotations are ignored when decoding
+        assert_eq!(
+            punycode_decode("b1abfaaepdrnnbgefbaDotcwatmq2g4l").unwrap(),
+            "почемужеонинеговорятпорусски"
+        );
+        assert_eq!(
+            punycode_decode("bcher-kv!"),
+            Err(NameError::InvalidPunycode {
+                label: String::from("bcher-kv!")
+            })
+        );
+    }
+
+    #[test]
+    fn test_idna() {
+        let vectors = [
+            ("bücher.sei", "xn--bcher-kva.sei"),
+            ("münchen", "xn--mnchen-3ya"),
+            ("españa", "xn--espaa-rta"),
+            ("日本語", "xn--wgv71a119e"),
+            ("한국", "xn--3e0b707e"),
+            ("рф", "xn--p1ai"),
+            ("☕", "xn--53h"),
+            ("💩", "xn--ls8h"),
+            ("alice.sei", "alice.sei"),
+        ];
+        for (unicode, ascii) in vectors {
+            assert_eq!(to_ascii(unicode).unwrap(), ascii);
+            assert_eq!(to_unicode(ascii).unwrap(), unicode);
+        }
+
+        // Both directions return the canonical form
+        assert_eq!(to_ascii("MÜNCHEN").unwrap(), "xn--mnchen-3ya");
+        assert_eq!(to_unicode("XN--MNCHEN-3YA").unwrap(), "münchen");
+
+        assert_eq!(
+            to_unicode("xn--alice-"),
+            Err(NameError::InvalidPunycode {
+                label: String::from("xn--alice-")
+            })
+        );
+        assert_eq!(
+            to_ascii(&"a".repeat(64)),
+            Err(NameError::LabelTooLong {
+                label: "a".repeat(64),
+                max_length: 63,
+            })
+        );
+        assert_eq!(
+            to_ascii("pаypal"),
+            Err(NameError::Normalize(NormalizeError::MixedScript {
+                first: "Latin".to_string(),
+                second: "Cyrillic".to_string(),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_dns_encoding() {
+        let encoded = dns_encode("alice.ust").unwrap();
+        assert_eq!(encoded, b"\x05alice\x03ust\x00".to_vec());
+        assert_eq!(dns_decode(&encoded).unwrap(), "alice.ust");
+        assert_eq!(namehash_from_dns(&encoded).unwrap(), namehash("alice.ust"));
+        assert_eq!(dns_encode("").unwrap(), vec![0]);
+        assert_eq!(dns_decode(&[0]).unwrap(), "");
+
+        // Labels are length-prefixed in bytes, not characters
+        let encoded = dns_encode("bücher.ust").unwrap();
+        assert_eq!(encoded[0], 7);
+        assert_eq!(dns_decode(&encoded).unwrap(), "bücher.ust");
+
+        // IDNA and upper-case labels hash to the canonical node
+        let encoded = dns_encode("xn--bcher-kva.ust").unwrap();
+        assert_eq!(namehash_from_dns(&encoded).unwrap(), namehash("bücher.ust"));
+        let encoded = dns_encode("ALICE.ust").unwrap();
+        assert_eq!(namehash_from_dns(&encoded).unwrap(), namehash("alice.ust"));
+        assert_eq!(namehash_from_dns(&[0]).unwrap(), namehash(""));
+
+        assert_eq!(dns_encode("alice..ust"), Err(NameError::EmptyLabel {}));
+        assert_eq!(
+            dns_encode(&"a".repeat(64)),
+            Err(NameError::LabelTooLong {
+                label: "a".repeat(64),
+                max_length: 63,
+            })
+        );
+        let invalid = |description: &str| {
+            Err(NameError::InvalidDnsName {
+                description: description.to_string(),
+            })
+        };
+        assert_eq!(dns_decode(b"\x05alice"), invalid("Missing root label"));
+        assert_eq!(
+            dns_decode(b"\x07alice\x00"),
+            invalid("Label is longer than the remaining input")
+        );
+        assert_eq!(
+            dns_decode(b"\x02\xff\xfe\x00"),
+            invalid("Label is not valid UTF-8")
+        );
+        assert_eq!(dns_decode(b"\x03a.b\x00"), invalid("Label contains a dot"));
+        let mut encoded = vec![64u8];
+        encoded.extend_from_slice("a".repeat(64).as_bytes());
+        encoded.push(0);
+        assert_eq!(dns_decode(&encoded), invalid("Label is longer than 63 bytes"));
+        // alice followed by a pointer to a suffix in place of the next label
+        assert_eq!(
+            dns_decode(b"\x05alice\xc0\x0c"),
+            invalid("Compression pointers are not supported")
+        );
+        assert_eq!(
+            dns_decode(b"\xc0\x00"),
+            invalid("Compression pointers are not supported")
+        );
+        assert_eq!(
+            dns_decode(b"\x03ust\x00\x00"),
+            invalid("Unexpected bytes after the root label")
+        );
+    }
+
     #[test]
     fn test_set_operator() {
         let mut deps = mock_dependencies();
@@ -156,7 +385,7 @@ mod tests {
             resolver: Some(String::from("resolver_address")),
             ttl: 1,
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         let res = query(
             deps.as_ref(),
@@ -194,7 +423,7 @@ mod tests {
             label: get_label_from_name(&String::from("ust")),
             owner: String::from("registrar_address"),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Check .ust record
         let res = query(
@@ -222,7 +451,7 @@ mod tests {
             label: get_label_from_name(&String::from("alice")), // alice label, // alice label
             owner: String::from("controller_address"),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Check alice.ust record
         let res = query(
@@ -268,7 +497,7 @@ mod tests {
             resolver: Some(String::from("resolver_address")),
             ttl: 1,
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         let res = query(
             deps.as_ref(),
@@ -345,7 +574,7 @@ mod tests {
             label: get_label_from_name(&String::from("ust")),
             owner: String::from("registrar_address"),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Check .ust record
         let res = query(
@@ -389,7 +618,7 @@ mod tests {
             label: get_label_from_name(&String::from("alice")), // alice label, // alice label
             owner: String::from("controller_address"),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Check alice.ust record
         let res = query(
@@ -428,7 +657,7 @@ mod tests {
             label: get_label_from_name(&String::from("ust")),
             owner: String::from("registrar_address"),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Check .ust record
         let res = query(
@@ -456,7 +685,7 @@ mod tests {
             label: get_label_from_name(&String::from("alice")), // alice label
             owner: String::from("controller_address"),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Check alice.ust record
         let res = query(
@@ -503,7 +732,7 @@ mod tests {
             node: subnode.clone(),
             owner: String::from("new_owner"),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Check alice.ust record
         let res = query(
@@ -542,7 +771,7 @@ mod tests {
             label: get_label_from_name(&String::from("ust")),
             owner: String::from("registrar_address"),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Check .ust record
         let res = query(
@@ -570,7 +799,7 @@ mod tests {
             label: get_label_from_name(&String::from("alice")), // alice label
             owner: String::from("controller_address"),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Check alice.ust record
         let res = query(
@@ -617,7 +846,7 @@ mod tests {
             node: subnode.clone(),
             ttl: 3,
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Check alice.ust record
         let res = query(
@@ -656,7 +885,7 @@ mod tests {
             label: get_label_from_name(&String::from("ust")),
             owner: String::from("registrar_address"),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Check .ust record
         let res = query(
@@ -684,7 +913,7 @@ mod tests {
             label: get_label_from_name(&String::from("alice")), // alice label
             owner: String::from("controller_address"),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Check alice.ust record
         let res = query(
@@ -731,7 +960,7 @@ mod tests {
             node: subnode.clone(),
             resolver: Some(String::from("new_resolver_address")),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Check alice.ust record
         let res = query(
@@ -770,7 +999,7 @@ mod tests {
             label: get_label_from_name(&String::from("ust")),
             owner: String::from("registrar_address"),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Register alice.ust
         let info = mock_info("registrar_address", &coins(0, "uusd"));
@@ -779,7 +1008,7 @@ mod tests {
             label: get_label_from_name(&String::from("alice")), // alice label
             owner: String::from("controller_address"),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Check alice.ust record
         let res = query(
@@ -822,7 +1051,7 @@ mod tests {
             label: get_label_from_name(&String::from("ust")),
             owner: String::from("registrar_address"),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         // Register alice.ust
         let info = mock_info("registrar_address", &coins(0, "uusd"));
@@ -831,7 +1060,7 @@ mod tests {
             label: get_label_from_name(&String::from("alice")), // alice label
             owner: String::from("controller_address"),
         };
-        assert_eq!(execute(deps.as_mut(), mock_env(), info, msg).is_ok(), true);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
 
         let res = query(
             deps.as_ref(),
@@ -843,7 +1072,7 @@ mod tests {
         )
         .unwrap();
         let value: bool = from_binary(&res).unwrap();
-        assert_eq!(value, true);
+        assert!(value);
 
         let res = query(
             deps.as_ref(),
@@ -855,7 +1084,7 @@ mod tests {
         )
         .unwrap();
         let value: bool = from_binary(&res).unwrap();
-        assert_eq!(value, false);
+        assert!(!value);
     }
 
     #[test]
@@ -953,4 +1182,51 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_get_record_by_dns_name() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {};
+        let info = mock_info("creator", &coins(0, "uusd"));
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let info = mock_info("creator", &coins(0, "uusd"));
+        let msg = ExecuteMsg::SetSubnodeOwner {
+            node: vec![0u8; 32],
+            label: get_label_from_name(&String::from("ust")),
+            owner: String::from("registrar_address"),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let info = mock_info("registrar_address", &coins(0, "uusd"));
+        let msg = ExecuteMsg::SetSubnodeOwner {
+            node: namehash("ust"),
+            label: get_label_from_name(&String::from("alice")),
+            owner: String::from("alice_address"),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetRecordByDnsName {
+                name: Binary::from(dns_encode("alice.ust").unwrap()),
+            },
+        )
+        .unwrap();
+        let value: RecordResponse = from_binary(&res).unwrap();
+        assert_eq!(value.owner, Addr::unchecked("alice_address"));
+
+        let err = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetRecordByDnsName {
+                name: Binary::from(b"\x05alice".to_vec()),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("InvalidDnsName: Missing root label")
+        );
+    }
 }
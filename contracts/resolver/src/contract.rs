@@ -20,10 +20,10 @@ pub fn instantiate(
     let registry_address = deps.api.addr_canonicalize(msg.registry_address.as_str())?;
     let trusted_reverse_registrar = deps
         .api
-        .addr_canonicalize(&msg.trusted_reverse_registrar.as_str())?;
+        .addr_canonicalize(msg.trusted_reverse_registrar.as_str())?;
     let trusted_controller = deps
         .api
-        .addr_canonicalize(&msg.trusted_controller.as_str())?;
+        .addr_canonicalize(msg.trusted_controller.as_str())?;
     let sender = deps.api.addr_canonicalize(info.sender.as_str())?;
     CONFIG.save(
         deps.storage,
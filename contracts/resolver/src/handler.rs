@@ -42,7 +42,7 @@ fn only_owner(deps: Deps, info: &MessageInfo) -> Result<bool, ContractError> {
 pub fn only_authorized(
     deps: &DepsMut,
     info: &MessageInfo,
-    node: &Vec<u8>,
+    node: &[u8],
 ) -> Result<bool, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -77,7 +77,7 @@ pub fn only_authorized(
     let is_node_owner: bool = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
         contract_addr: registry_address.clone(),
         msg: to_binary(&RegistryQueryMsg::GetIsNodeOwner {
-            node: node.clone(),
+            node: node.to_vec(),
             address: info.sender.to_string(),
         })?,
     }))?;
@@ -85,10 +85,10 @@ pub fn only_authorized(
     if is_node_owner {
         return Ok(true);
     }
-    return Err(ContractError::NotNodeOwner {
+    Err(ContractError::NotNodeOwner {
         sender: info.sender.to_string(),
-        node: format!("{:?}", node.clone()),
-    });
+        node: format!("{:?}", node),
+    })
 }
 
 pub fn set_address(
@@ -123,7 +123,7 @@ pub fn set_sei_address(
     address: String,
 ) -> Result<Response, ContractError> {
     let sei_address = deps.api.addr_validate(address.as_str())?;
-    return set_address(deps, env, info, node, sei_address.to_string());
+    set_address(deps, env, info, node, sei_address.to_string())
 }
 
 pub fn set_name(
@@ -134,7 +134,7 @@ pub fn set_name(
     name: String,
 ) -> Result<Response, ContractError> {
     // let namehash_node = namehash(&name);
-    let address_namehash = namehash((address.clone() + &".addr.reverse".to_string()).as_str());
+    let address_namehash = namehash((address.clone() + ".addr.reverse").as_str());
 
     // only_authorized(&deps, &info, &namehash_node)?;
     only_authorized(&deps, &info, &address_namehash)?;
@@ -160,7 +160,7 @@ pub fn query_name(deps: Deps, _env: Env, node: Vec<u8>) -> StdResult<NameRespons
 }
 
 pub fn query_sei_address(deps: Deps, env: Env, node: Vec<u8>) -> StdResult<AddressResponse> {
-    return query_address(deps, env, node);
+    query_address(deps, env, node)
 }
 
 pub fn set_text_data(
@@ -264,7 +264,7 @@ fn process_wasm_query(address: Addr, binary: Binary) -> StdResult<Vec<u8>> {
         contract_addr: address.to_string(),
         msg: binary,
     });
-    return to_vec(&query);
+    to_vec(&query)
 }
 
 fn process_query_result(result: QuerierResult) -> QueryResult {
@@ -279,9 +279,7 @@ fn process_query_result(result: QuerierResult) -> QueryResult {
 
 pub fn multicall(deps: Deps, env: Env, queries: Vec<Binary>) -> StdResult<MulticallResponse> {
     let mut results: Vec<Binary> = Vec::new();
-    let n = queries.len();
-    for i in 0..n {
-        let query = queries[i].clone();
+    for (i, query) in queries.into_iter().enumerate() {
         let wasm = &process_wasm_query(env.contract.address.clone(), query).unwrap_or(vec![]);
         let res = deps.querier.raw_query(wasm);
         let data = match process_query_result(res) {
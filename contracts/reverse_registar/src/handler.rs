@@ -35,7 +35,7 @@ pub fn set_config(
     let mut config = CONFIG.load(deps.storage)?;
     config.owner = deps.api.addr_canonicalize(owner.as_str())?;
     config.resolver_address = deps.api.addr_canonicalize(resolver_address.as_str())?;
-    config.registry_address = deps.api.addr_canonicalize(&registry_address.as_str())?;
+    config.registry_address = deps.api.addr_canonicalize(registry_address.as_str())?;
     CONFIG.save(deps.storage, &config)?;
     Ok(Response::default())
 }
@@ -84,11 +84,11 @@ pub fn claim_for_addr(
     let config = CONFIG.load(deps.storage)?;
     let registry_address = deps.api.addr_humanize(&config.registry_address)?;
     let labelhash = get_label_from_name(&address);
-    let reverse_node = namehash((address + &".addr.reverse".to_string()).as_str());
+    let reverse_node = namehash((address + ".addr.reverse").as_str());
     let set_subnode_owner_registry_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: registry_address.to_string(),
         msg: to_binary(&RegistryExecuteMsg::SetSubnodeOwner {
-            node: namehash(&"addr.reverse".to_string()),
+            node: namehash("addr.reverse"),
             owner: contract_address.to_string(),
             label: labelhash,
         })?,
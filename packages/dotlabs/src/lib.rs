@@ -1,4 +1,6 @@
 mod error;
+pub mod name_policy;
+pub mod normalize;
 pub mod registrar;
 pub mod registry;
 pub mod resolver;
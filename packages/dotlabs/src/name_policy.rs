@@ -0,0 +1,148 @@
+use cosmwasm_schema::cw_serde;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::normalize::{
+    is_emoji, is_emoji_modifier, is_emoji_tag, is_text_emoji, normalize_label, KEYCAP, ZWJ,
+};
+
+#[cw_serde]
+pub enum CharacterClass {
+    /// `0-9`
+    Digit {},
+    /// `a-z`
+    Latin {},
+    Hyphen {},
+    Underscore {},
+    /// Emoji, including the joiners and modifiers of emoji sequences
+    Emoji {},
+    /// Any other character accepted by `normalize`
+    Unicode {},
+}
+
+impl CharacterClass {
+    pub fn of(c: char) -> CharacterClass {
+        match c {
+            '0'..='9' => CharacterClass::Digit {},
+            'a'..='z' => CharacterClass::Latin {},
+            '-' => CharacterClass::Hyphen {},
+            '_' => CharacterClass::Underscore {},
+            ZWJ | KEYCAP => CharacterClass::Emoji {},
+            c if is_emoji(c) || is_text_emoji(c) || is_emoji_modifier(c) || is_emoji_tag(c) => {
+                CharacterClass::Emoji {}
+            }
+            _ => CharacterClass::Unicode {},
+        }
+    }
+}
+
+/// Rules a label must follow on top of normalization. Lengths are in graphemes.
+#[cw_serde]
+pub struct NamePolicy {
+    pub min_length: u32,
+    pub max_length: Option<u32>,
+    pub allowed_characters: Vec<CharacterClass>,
+    pub allow_leading_hyphen: bool,
+    pub allow_trailing_hyphen: bool,
+    pub allow_consecutive_hyphens: bool,
+    /// Canonical substrings no label may contain
+    pub blocked_substrings: Vec<String>,
+}
+
+impl Default for NamePolicy {
+    fn default() -> Self {
+        NamePolicy {
+            min_length: 1,
+            max_length: None,
+            allowed_characters: vec![
+                CharacterClass::Digit {},
+                CharacterClass::Latin {},
+                CharacterClass::Hyphen {},
+                CharacterClass::Underscore {},
+                CharacterClass::Emoji {},
+                CharacterClass::Unicode {},
+            ],
+            allow_leading_hyphen: false,
+            allow_trailing_hyphen: false,
+            allow_consecutive_hyphens: true,
+            blocked_substrings: vec![],
+        }
+    }
+}
+
+/// Rule a label failed to follow.
+#[cw_serde]
+pub enum NameRule {
+    /// The label is not in its canonical form, see `normalize_label`.
+    Normalization {
+        reason: String,
+    },
+    MinLength {
+        min_length: u32,
+    },
+    MaxLength {
+        max_length: u32,
+    },
+    DisallowedCharacter {
+        character: String,
+    },
+    LeadingHyphen {},
+    TrailingHyphen {},
+    ConsecutiveHyphens {},
+    BlockedSubstring {
+        substring: String,
+    },
+}
+
+/// Checks a single label against the policy and returns the first rule it fails.
+pub fn validate_label(name: &str, policy: &NamePolicy) -> Result<(), NameRule> {
+    match normalize_label(name) {
+        Ok(label) if label == name => {}
+        Ok(label) => {
+            return Err(NameRule::Normalization {
+                reason: format!("The canonical form is {}.", label),
+            })
+        }
+        Err(err) => {
+            return Err(NameRule::Normalization {
+                reason: err.to_string(),
+            })
+        }
+    }
+
+    let length = name.graphemes(true).count() as u32;
+    if length < policy.min_length {
+        return Err(NameRule::MinLength {
+            min_length: policy.min_length,
+        });
+    }
+    if let Some(max_length) = policy.max_length.filter(|max_length| length > *max_length) {
+        return Err(NameRule::MaxLength { max_length });
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| !policy.allowed_characters.contains(&CharacterClass::of(*c)))
+    {
+        return Err(NameRule::DisallowedCharacter {
+            character: c.to_string(),
+        });
+    }
+    if !policy.allow_leading_hyphen && name.starts_with('-') {
+        return Err(NameRule::LeadingHyphen {});
+    }
+    if !policy.allow_trailing_hyphen && name.ends_with('-') {
+        return Err(NameRule::TrailingHyphen {});
+    }
+    if !policy.allow_consecutive_hyphens && name.contains("--") {
+        return Err(NameRule::ConsecutiveHyphens {});
+    }
+    if let Some(substring) = policy
+        .blocked_substrings
+        .iter()
+        .find(|substring| name.contains(substring.as_str()))
+    {
+        return Err(NameRule::BlockedSubstring {
+            substring: substring.clone(),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,233 @@
+use thiserror::Error;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+use unicode_script::{Script, UnicodeScript};
+
+pub(crate) const ZWJ: char = '\u{200D}';
+const VARIATION_SELECTOR_16: char = '\u{FE0F}';
+pub(crate) const KEYCAP: char = '\u{20E3}';
+
+// Scripts that are written together and may share a label
+const SCRIPT_GROUPS: &[&[Script]] = &[
+    &[Script::Han, Script::Hiragana, Script::Katakana],
+    &[Script::Han, Script::Hangul],
+    &[Script::Han, Script::Bopomofo],
+];
+
+// Cyrillic and Greek letters that render like Latin ones. This is a short hand-picked list,
+// not the Unicode confusables data, so it only catches the most common lookalikes
+const LATIN_LOOKALIKES: &[char] = &[
+    'а', 'е', 'о', 'р', 'с', 'у', 'х', 'ѕ', 'і', 'ј', 'һ', 'ӏ', 'ԁ', 'ԛ', 'ԝ', 'α', 'ι', 'κ', 'ν',
+    'ο', 'ρ', 'υ',
+];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum NormalizeError {
+    #[error("EmptyLabel: Names cannot contain empty labels.")]
+    EmptyLabel {},
+
+    #[error("DisallowedCharacter: {character:?} is not allowed in names.")]
+    DisallowedCharacter { character: char },
+
+    #[error("InvalidUnderscore: Underscores are only allowed at the start of a label.")]
+    InvalidUnderscore {},
+
+    #[error("InvalidLabelExtension: Hyphens at positions 3 and 4 are reserved.")]
+    InvalidLabelExtension {},
+
+    #[error("LeadingCombiningMark: {character:?} cannot start a label.")]
+    LeadingCombiningMark { character: char },
+
+    #[error("InvalidEmojiSequence: {character:?} must follow an emoji.")]
+    InvalidEmojiSequence { character: char },
+
+    #[error("MixedScript: The label mixes {first} and {second} characters.")]
+    MixedScript { first: String, second: String },
+
+    #[error("Confusable: {label} can be mistaken for a Latin name.")]
+    Confusable { label: String },
+}
+
+// Code points with the Emoji_Presentation property (Unicode 15.0 emoji-data.txt)
+#[rustfmt::skip]
+const EMOJI_PRESENTATION: &[(u32, u32)] = &[
+    (0x231A, 0x231B), (0x23E9, 0x23EC), (0x23F0, 0x23F0), (0x23F3, 0x23F3), (0x25FD, 0x25FE),
+    (0x2614, 0x2615), (0x2648, 0x2653), (0x267F, 0x267F), (0x2693, 0x2693), (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB), (0x26BD, 0x26BE), (0x26C4, 0x26C5), (0x26CE, 0x26CE), (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA), (0x26F2, 0x26F3), (0x26F5, 0x26F5), (0x26FA, 0x26FA), (0x26FD, 0x26FD),
+    (0x2705, 0x2705), (0x270A, 0x270B), (0x2728, 0x2728), (0x274C, 0x274C), (0x274E, 0x274E),
+    (0x2753, 0x2755), (0x2757, 0x2757), (0x2795, 0x2797), (0x27B0, 0x27B0), (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C), (0x2B50, 0x2B50), (0x2B55, 0x2B55), (0x1F004, 0x1F004), (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E), (0x1F191, 0x1F19A), (0x1F1E6, 0x1F1FF), (0x1F201, 0x1F201),
+    (0x1F21A, 0x1F21A), (0x1F22F, 0x1F22F), (0x1F232, 0x1F236), (0x1F238, 0x1F23A),
+    (0x1F250, 0x1F251), (0x1F300, 0x1F320), (0x1F32D, 0x1F335), (0x1F337, 0x1F37C),
+    (0x1F37E, 0x1F393), (0x1F3A0, 0x1F3CA), (0x1F3CF, 0x1F3D3), (0x1F3E0, 0x1F3F0),
+    (0x1F3F4, 0x1F3F4), (0x1F3F8, 0x1F43E), (0x1F440, 0x1F440), (0x1F442, 0x1F4FC),
+    (0x1F4FF, 0x1F53D), (0x1F54B, 0x1F54E), (0x1F550, 0x1F567), (0x1F57A, 0x1F57A),
+    (0x1F595, 0x1F596), (0x1F5A4, 0x1F5A4), (0x1F5FB, 0x1F64F), (0x1F680, 0x1F6C5),
+    (0x1F6CC, 0x1F6CC), (0x1F6D0, 0x1F6D2), (0x1F6D5, 0x1F6D7), (0x1F6DC, 0x1F6DF),
+    (0x1F6EB, 0x1F6EC), (0x1F6F4, 0x1F6FC), (0x1F7E0, 0x1F7EB), (0x1F7F0, 0x1F7F0),
+    (0x1F90C, 0x1F93A), (0x1F93C, 0x1F945), (0x1F947, 0x1F9FF), (0x1FA70, 0x1FA7C),
+    (0x1FA80, 0x1FA88), (0x1FA90, 0x1FABD), (0x1FABF, 0x1FAC5), (0x1FACE, 0x1FADB),
+    (0x1FAE0, 0x1FAE8), (0x1FAF0, 0x1FAF8),
+];
+
+// Code points with the Emoji property that default to text presentation, without the
+// keycap bases `#`, `*` and `0-9` (Unicode 15.0 emoji-data.txt)
+#[rustfmt::skip]
+const TEXT_EMOJI: &[(u32, u32)] = &[
+    (0x00A9, 0x00A9), (0x00AE, 0x00AE), (0x203C, 0x203C), (0x2049, 0x2049), (0x2122, 0x2122),
+    (0x2139, 0x2139), (0x2194, 0x2199), (0x21A9, 0x21AA), (0x2328, 0x2328), (0x23CF, 0x23CF),
+    (0x23ED, 0x23EF), (0x23F1, 0x23F2), (0x23F8, 0x23FA), (0x24C2, 0x24C2), (0x25AA, 0x25AB),
+    (0x25B6, 0x25B6), (0x25C0, 0x25C0), (0x25FB, 0x25FC), (0x2600, 0x2604), (0x260E, 0x260E),
+    (0x2611, 0x2611), (0x2618, 0x2618), (0x261D, 0x261D), (0x2620, 0x2620), (0x2622, 0x2623),
+    (0x2626, 0x2626), (0x262A, 0x262A), (0x262E, 0x262F), (0x2638, 0x263A), (0x2640, 0x2640),
+    (0x2642, 0x2642), (0x265F, 0x2660), (0x2663, 0x2663), (0x2665, 0x2666), (0x2668, 0x2668),
+    (0x267B, 0x267B), (0x267E, 0x267E), (0x2692, 0x2692), (0x2694, 0x2697), (0x2699, 0x2699),
+    (0x269B, 0x269C), (0x26A0, 0x26A0), (0x26A7, 0x26A7), (0x26B0, 0x26B1), (0x26C8, 0x26C8),
+    (0x26CF, 0x26CF), (0x26D1, 0x26D1), (0x26D3, 0x26D3), (0x26E9, 0x26E9), (0x26F0, 0x26F1),
+    (0x26F4, 0x26F4), (0x26F7, 0x26F9), (0x2702, 0x2702), (0x2708, 0x2709), (0x270C, 0x270D),
+    (0x270F, 0x270F), (0x2712, 0x2712), (0x2714, 0x2714), (0x2716, 0x2716), (0x271D, 0x271D),
+    (0x2721, 0x2721), (0x2733, 0x2734), (0x2744, 0x2744), (0x2747, 0x2747), (0x2763, 0x2764),
+    (0x27A1, 0x27A1), (0x2934, 0x2935), (0x2B05, 0x2B07), (0x3030, 0x3030), (0x303D, 0x303D),
+    (0x3297, 0x3297), (0x3299, 0x3299), (0x1F170, 0x1F171), (0x1F17E, 0x1F17F), (0x1F202, 0x1F202),
+    (0x1F237, 0x1F237), (0x1F321, 0x1F321), (0x1F324, 0x1F32C), (0x1F336, 0x1F336),
+    (0x1F37D, 0x1F37D), (0x1F396, 0x1F397), (0x1F399, 0x1F39B), (0x1F39E, 0x1F39F),
+    (0x1F3CB, 0x1F3CE), (0x1F3D4, 0x1F3DF), (0x1F3F3, 0x1F3F3), (0x1F3F5, 0x1F3F5),
+    (0x1F3F7, 0x1F3F7), (0x1F43F, 0x1F43F), (0x1F441, 0x1F441), (0x1F4FD, 0x1F4FD),
+    (0x1F549, 0x1F54A), (0x1F56F, 0x1F570), (0x1F573, 0x1F579), (0x1F587, 0x1F587),
+    (0x1F58A, 0x1F58D), (0x1F590, 0x1F590), (0x1F5A5, 0x1F5A5), (0x1F5A8, 0x1F5A8),
+    (0x1F5B1, 0x1F5B2), (0x1F5BC, 0x1F5BC), (0x1F5C2, 0x1F5C4), (0x1F5D1, 0x1F5D3),
+    (0x1F5DC, 0x1F5DE), (0x1F5E1, 0x1F5E1), (0x1F5E3, 0x1F5E3), (0x1F5E8, 0x1F5E8),
+    (0x1F5EF, 0x1F5EF), (0x1F5F3, 0x1F5F3), (0x1F5FA, 0x1F5FA), (0x1F6CB, 0x1F6CB),
+    (0x1F6CD, 0x1F6CF), (0x1F6E0, 0x1F6E5), (0x1F6E9, 0x1F6E9), (0x1F6F0, 0x1F6F0),
+    (0x1F6F3, 0x1F6F3),
+];
+
+fn in_ranges(ranges: &[(u32, u32)], c: char) -> bool {
+    ranges
+        .iter()
+        .any(|(first, last)| (*first..=*last).contains(&(c as u32)))
+}
+
+/// Emoji that stand on their own.
+pub(crate) fn is_emoji(c: char) -> bool {
+    in_ranges(EMOJI_PRESENTATION, c)
+}
+
+/// Emoji shown as text symbols unless followed by U+FE0F. Since the canonical form drops the
+/// selector, they are only accepted inside modifier and ZWJ sequences.
+pub(crate) fn is_text_emoji(c: char) -> bool {
+    in_ranges(TEXT_EMOJI, c)
+}
+
+pub(crate) fn is_emoji_modifier(c: char) -> bool {
+    matches!(c as u32, 0x1F3FB..=0x1F3FF)
+}
+
+pub(crate) fn is_emoji_tag(c: char) -> bool {
+    matches!(c as u32, 0xE0020..=0xE007F)
+}
+
+/// Normalizes a dotted name label by label. See `normalize_label`.
+pub fn normalize(name: &str) -> Result<String, NormalizeError> {
+    Ok(name
+        .split('.')
+        .map(normalize_label)
+        .collect::<Result<Vec<String>, NormalizeError>>()?
+        .join("."))
+}
+
+/// Returns the canonical form of a single label, following ENSIP-15:
+/// NFC and case folding, then validation of the characters, emoji sequences and scripts.
+/// Emoji presentation selectors (U+FE0F) are dropped from the canonical form, so text-default
+/// emoji such as U+2764 are only accepted as part of a sequence. Labels made only of Cyrillic or
+/// Greek lookalikes of Latin letters are rejected, from a partial list of confusables.
+pub fn normalize_label(label: &str) -> Result<String, NormalizeError> {
+    let label = label
+        .nfc()
+        .flat_map(char::to_lowercase)
+        .nfc()
+        .filter(|c| *c != VARIATION_SELECTOR_16)
+        .collect::<String>();
+    let chars = label.chars().collect::<Vec<char>>();
+    if chars.is_empty() {
+        return Err(NormalizeError::EmptyLabel {});
+    }
+    if chars.len() >= 4 && chars[2] == '-' && chars[3] == '-' {
+        return Err(NormalizeError::InvalidLabelExtension {});
+    }
+
+    let mut scripts: Vec<Script> = vec![];
+    for (i, &c) in chars.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|j| chars[j]);
+        let next = chars.get(i + 1).copied();
+        let follows_emoji =
+            prev.is_some_and(|p| is_emoji(p) || is_text_emoji(p) || is_emoji_tag(p));
+        match c {
+            '-' => {}
+            '_' => {
+                if chars[..i].iter().any(|p| *p != '_') {
+                    return Err(NormalizeError::InvalidUnderscore {});
+                }
+            }
+            ZWJ => {
+                if !follows_emoji || !next.is_some_and(|n| is_emoji(n) || is_text_emoji(n)) {
+                    return Err(NormalizeError::InvalidEmojiSequence { character: c });
+                }
+            }
+            KEYCAP => {
+                if !prev.is_some_and(|p| p.is_ascii_digit()) {
+                    return Err(NormalizeError::InvalidEmojiSequence { character: c });
+                }
+            }
+            c if is_emoji_modifier(c) || is_emoji_tag(c) => {
+                if !follows_emoji {
+                    return Err(NormalizeError::InvalidEmojiSequence { character: c });
+                }
+            }
+            c if is_emoji(c) => {}
+            c if is_text_emoji(c) => {
+                let in_sequence =
+                    prev == Some(ZWJ) || next.is_some_and(|n| n == ZWJ || is_emoji_modifier(n));
+                if !in_sequence {
+                    return Err(NormalizeError::DisallowedCharacter { character: c });
+                }
+            }
+            c if is_combining_mark(c) => {
+                if i == 0 {
+                    return Err(NormalizeError::LeadingCombiningMark { character: c });
+                }
+            }
+            // Compatibility forms such as full-width letters or ligatures are rejected
+            c if c.is_alphanumeric() && c.to_string().nfkc().eq(std::iter::once(c)) => {
+                let script = c.script();
+                if !matches!(script, Script::Common | Script::Inherited | Script::Unknown)
+                    && !scripts.contains(&script)
+                {
+                    scripts.push(script);
+                }
+            }
+            c => return Err(NormalizeError::DisallowedCharacter { character: c }),
+        }
+    }
+
+    if scripts.len() > 1
+        && !SCRIPT_GROUPS
+            .iter()
+            .any(|group| scripts.iter().all(|script| group.contains(script)))
+    {
+        return Err(NormalizeError::MixedScript {
+            first: scripts[0].full_name().to_string(),
+            second: scripts[1].full_name().to_string(),
+        });
+    }
+    if matches!(scripts[..], [Script::Cyrillic] | [Script::Greek])
+        && chars
+            .iter()
+            .filter(|c| c.is_alphabetic())
+            .all(|c| LATIN_LOOKALIKES.contains(c))
+    {
+        return Err(NormalizeError::Confusable { label });
+    }
+    Ok(label)
+}
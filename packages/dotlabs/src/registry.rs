@@ -1,4 +1,4 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Binary};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -54,6 +54,8 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     GetRecord { name: String },
     GetRecordByNode { node: Vec<u8> },
+    /// Looks up a record by a name in DNS wire format, see `dns_encode`.
+    GetRecordByDnsName { name: Binary },
     GetIsNodeOwner { node: Vec<u8>, address: String },
     GetIsApprovedForAll { owner: String, operator: String },
     GetConfig {},
@@ -1,6 +1,8 @@
+use crate::normalize::{normalize, normalize_label, NormalizeError};
 use base64::{engine::general_purpose, Engine as _};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use hex;
+use thiserror::Error;
 use tiny_keccak::{Hasher, Keccak};
 use unicode_segmentation::UnicodeSegmentation;
 
@@ -10,11 +12,22 @@ pub fn keccak256(i: &[u8]) -> Vec<u8> {
     keccak256.update(i);
     keccak256.finalize(&mut o);
     // Keccak::keccak256(i, &mut o);
-    return o;
+    o
 }
 
+/// Canonical form of every label of `name`. A label that cannot be normalized is kept as given,
+/// without affecting the others. The controller rejects such labels before they are registered.
+pub fn canonical_labels(name: &str) -> String {
+    name.split('.')
+        .map(|label| normalize_label(label).unwrap_or_else(|_| label.to_string()))
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+// Names are hashed in their canonical form, see `canonical_labels`
+#[allow(clippy::ptr_arg)]
 pub fn get_label_from_name(name: &String) -> Vec<u8> {
-    keccak256(name.as_bytes())
+    keccak256(canonical_labels(name).as_bytes())
 }
 
 pub fn get_token_id_from_label(label: &Vec<u8>) -> String {
@@ -26,6 +39,7 @@ pub fn namehash(name: &str) -> Vec<u8> {
     if name.is_empty() {
         return node;
     }
+    let name = canonical_labels(name);
     let mut labels: Vec<&str> = name.split(".").collect();
     labels.reverse();
 
@@ -51,7 +65,294 @@ pub fn convert_namehash_to_hex_string(namehash: Vec<u8>) -> String {
     hex::encode(namehash)
 }
 
-const COLORS: &'static [&'static [&str]] = &[
+#[derive(Error, Debug, PartialEq)]
+pub enum NameError {
+    #[error("{0}")]
+    Normalize(#[from] NormalizeError),
+
+    #[error("InvalidPunycode: {label} is not valid punycode.")]
+    InvalidPunycode { label: String },
+
+    #[error("LabelTooLong: {label} is longer than {max_length} bytes.")]
+    LabelTooLong { label: String, max_length: usize },
+
+    #[error("EmptyLabel: Names cannot contain empty labels.")]
+    EmptyLabel {},
+
+    #[error("InvalidDnsName: {description}")]
+    InvalidDnsName { description: String },
+}
+
+// Punycode parameters, see RFC 3492 section 5
+const BASE: u32 = 36;
+const T_MIN: u32 = 1;
+const T_MAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 128;
+
+const ACE_PREFIX: &str = "xn--";
+pub const MAX_LABEL_LENGTH: usize = 63;
+// Wire format labels carry UTF-8 names and are capped in bytes (RFC 1035 section 2.3.4)
+pub const MAX_DNS_LABEL_LENGTH: usize = 63;
+// Length bytes with both high bits set are compression pointers (RFC 1035 section 4.1.4)
+const DNS_POINTER_MASK: u8 = 0xC0;
+
+fn threshold(k: u32, bias: u32) -> u32 {
+    if k <= bias {
+        T_MIN
+    } else if k >= bias + T_MAX {
+        T_MAX
+    } else {
+        k - bias
+    }
+}
+
+fn adapt(delta: u32, num_points: u32, first_time: bool) -> u32 {
+    let mut delta = if first_time { delta / DAMP } else { delta / 2 };
+    delta += delta / num_points;
+    let mut k = 0;
+    while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+        delta /= BASE - T_MIN;
+        k += BASE;
+    }
+    k + (BASE - T_MIN + 1) * delta / (delta + SKEW)
+}
+
+fn encode_digit(digit: u32) -> char {
+    match digit {
+        0..=25 => (b'a' + digit as u8) as char,
+        _ => (b'0' + (digit - 26) as u8) as char,
+    }
+}
+
+fn decode_digit(byte: u8) -> Option<u32> {
+    match byte {
+        b'a'..=b'z' => Some((byte - b'a') as u32),
+        b'A'..=b'Z' => Some((byte - b'A') as u32),
+        b'0'..=b'9' => Some((byte - b'0') as u32 + 26),
+        _ => None,
+    }
+}
+
+/// Encodes a label with Punycode (RFC 3492), without the `xn--` prefix.
+pub fn punycode_encode(label: &str) -> Result<String, NameError> {
+    let invalid = || NameError::InvalidPunycode {
+        label: label.to_string(),
+    };
+    let code_points = label.chars().map(|c| c as u32).collect::<Vec<u32>>();
+    let mut output = label.chars().filter(char::is_ascii).collect::<String>();
+    let basic_length = output.len() as u32;
+    if basic_length > 0 {
+        output.push('-');
+    }
+
+    let mut n = INITIAL_N;
+    let mut delta: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut handled = basic_length;
+    while (handled as usize) < code_points.len() {
+        let m = *code_points
+            .iter()
+            .filter(|c| **c >= n)
+            .min()
+            .ok_or_else(invalid)?;
+        delta = (m - n)
+            .checked_mul(handled + 1)
+            .and_then(|increment| delta.checked_add(increment))
+            .ok_or_else(invalid)?;
+        n = m;
+        for &c in code_points.iter() {
+            if c < n {
+                delta = delta.checked_add(1).ok_or_else(invalid)?;
+            }
+            if c == n {
+                let mut q = delta;
+                let mut k = BASE;
+                loop {
+                    let t = threshold(k, bias);
+                    if q < t {
+                        break;
+                    }
+                    output.push(encode_digit(t + (q - t) % (BASE - t)));
+                    q = (q - t) / (BASE - t);
+                    k += BASE;
+                }
+                output.push(encode_digit(q));
+                bias = adapt(delta, handled + 1, handled == basic_length);
+                delta = 0;
+                handled += 1;
+            }
+        }
+        delta = delta.checked_add(1).ok_or_else(invalid)?;
+        n += 1;
+    }
+    Ok(output)
+}
+
+/// Decodes a Punycode (RFC 3492) label given without the `xn--` prefix.
+pub fn punycode_decode(label: &str) -> Result<String, NameError> {
+    let invalid = || NameError::InvalidPunycode {
+        label: label.to_string(),
+    };
+    let (basic, extended) = match label.rfind('-') {
+        Some(index) => (&label[..index], &label[index + 1..]),
+        None => ("", label),
+    };
+    if !basic.is_ascii() {
+        return Err(invalid());
+    }
+
+    let mut output = basic.chars().collect::<Vec<char>>();
+    let mut n = INITIAL_N;
+    let mut i: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut bytes = extended.bytes().peekable();
+    while bytes.peek().is_some() {
+        let old_i = i;
+        let mut w: u32 = 1;
+        let mut k = BASE;
+        loop {
+            let digit = bytes.next().and_then(decode_digit).ok_or_else(invalid)?;
+            i = digit
+                .checked_mul(w)
+                .and_then(|increment| i.checked_add(increment))
+                .ok_or_else(invalid)?;
+            let t = threshold(k, bias);
+            if digit < t {
+                break;
+            }
+            w = w.checked_mul(BASE - t).ok_or_else(invalid)?;
+            k += BASE;
+        }
+        let length = output.len() as u32 + 1;
+        bias = adapt(i - old_i, length, old_i == 0);
+        n = n.checked_add(i / length).ok_or_else(invalid)?;
+        i %= length;
+        output.insert(i as usize, char::from_u32(n).ok_or_else(invalid)?);
+        i += 1;
+    }
+    Ok(output.into_iter().collect())
+}
+
+/// Converts a name to its canonical Unicode form, decoding `xn--` labels.
+pub fn to_unicode(name: &str) -> Result<String, NameError> {
+    let labels = name
+        .split('.')
+        .map(|label| match label.get(..ACE_PREFIX.len()) {
+            Some(prefix) if prefix.eq_ignore_ascii_case(ACE_PREFIX) => {
+                let encoded = &label[ACE_PREFIX.len()..];
+                let decoded = punycode_decode(encoded)?;
+                // Reject encoded ASCII labels and encodings that do not round trip
+                if decoded.is_ascii() || !punycode_encode(&decoded)?.eq_ignore_ascii_case(encoded) {
+                    return Err(NameError::InvalidPunycode {
+                        label: label.to_string(),
+                    });
+                }
+                Ok(decoded)
+            }
+            _ => Ok(label.to_string()),
+        })
+        .collect::<Result<Vec<String>, NameError>>()?;
+    Ok(normalize(&labels.join("."))?)
+}
+
+/// Converts a name to its ASCII compatible form, encoding non-ASCII labels as `xn--` labels.
+pub fn to_ascii(name: &str) -> Result<String, NameError> {
+    let labels = to_unicode(name)?
+        .split('.')
+        .map(|label| {
+            let label = if label.is_ascii() {
+                label.to_string()
+            } else {
+                format!("{}{}", ACE_PREFIX, punycode_encode(label)?)
+            };
+            if label.len() > MAX_LABEL_LENGTH {
+                return Err(NameError::LabelTooLong {
+                    label,
+                    max_length: MAX_LABEL_LENGTH,
+                });
+            }
+            Ok(label)
+        })
+        .collect::<Result<Vec<String>, NameError>>()?;
+    Ok(labels.join("."))
+}
+
+/// Encodes a name in DNS wire format: every label prefixed with its length in bytes,
+/// terminated by the empty root label.
+pub fn dns_encode(name: &str) -> Result<Vec<u8>, NameError> {
+    let mut encoded = vec![];
+    if !name.is_empty() {
+        for label in name.split('.') {
+            if label.is_empty() {
+                return Err(NameError::EmptyLabel {});
+            }
+            if label.len() > MAX_DNS_LABEL_LENGTH {
+                return Err(NameError::LabelTooLong {
+                    label: label.to_string(),
+                    max_length: MAX_DNS_LABEL_LENGTH,
+                });
+            }
+            encoded.push(label.len() as u8);
+            encoded.extend_from_slice(label.as_bytes());
+        }
+    }
+    encoded.push(0);
+    Ok(encoded)
+}
+
+/// Decodes a name from DNS wire format. The input must end with its root label, labels are at
+/// most 63 bytes and compression pointers are rejected.
+pub fn dns_decode(encoded: &[u8]) -> Result<String, NameError> {
+    let invalid = |description: &str| NameError::InvalidDnsName {
+        description: description.to_string(),
+    };
+    let mut labels = vec![];
+    let mut offset = 0;
+    loop {
+        let length = *encoded
+            .get(offset)
+            .ok_or_else(|| invalid("Missing root label"))?;
+        if length & DNS_POINTER_MASK == DNS_POINTER_MASK {
+            return Err(invalid("Compression pointers are not supported"));
+        }
+        let length = length as usize;
+        if length > MAX_DNS_LABEL_LENGTH {
+            return Err(invalid("Label is longer than 63 bytes"));
+        }
+        offset += 1;
+        if length == 0 {
+            break;
+        }
+        let label = encoded
+            .get(offset..offset + length)
+            .ok_or_else(|| invalid("Label is longer than the remaining input"))?;
+        let label = std::str::from_utf8(label).map_err(|_| invalid("Label is not valid UTF-8"))?;
+        if label.contains('.') {
+            return Err(invalid("Label contains a dot"));
+        }
+        labels.push(label);
+        offset += length;
+    }
+    if offset != encoded.len() {
+        return Err(invalid("Unexpected bytes after the root label"));
+    }
+    Ok(labels.join("."))
+}
+
+/// Hashes a DNS-encoded name in its canonical Unicode form, so `xn--` and
+/// non-canonical labels resolve to the node they were registered under.
+pub fn namehash_from_dns(encoded: &[u8]) -> Result<Vec<u8>, NameError> {
+    let name = dns_decode(encoded)?;
+    if name.is_empty() {
+        return Ok(namehash(&name));
+    }
+    Ok(namehash(&to_unicode(&name)?))
+}
+
+const COLORS: &[&[&str]] = &[
     &["#F5A4C7", "#F5A4C7", "#FF6483"],
     &["#A1A3A5", "#F5A4C7", "#636466"],
     &["#ABAAF9", "#ABAAF9", "#2A4EF5"],
@@ -90,8 +391,7 @@ pub fn generate_image(name: String, timestamp: u64) -> String {
         .collect::<Vec<String>>();
     let n_line = names.len();
     let mut name_tags = String::from("");
-    for i in 0..n_line {
-        let name = names[i].clone();
+    for (i, name) in names.iter().enumerate() {
         let y = if n_line == 1 {
             245
         } else {
@@ -108,7 +408,7 @@ pub fn generate_image(name: String, timestamp: u64) -> String {
         );
         name_tags += &name_tag;
     }
-    return String::from("data:image/svg+xml;base64,")
+    String::from("data:image/svg+xml;base64,")
         // + &base64::encode(format!(
         + &general_purpose::STANDARD.encode(format!(
             r###"
@@ -148,5 +448,5 @@ pub fn generate_image(name: String, timestamp: u64) -> String {
             c1 = color[1],
             c2 = color[2],
             name_tags = name_tags
-        ));
+        ))
 }
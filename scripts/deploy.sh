@@ -70,19 +70,39 @@ printf  "Reverse Registrar Address : $addrReverse \n" >> contracts.txt
 
 
 #Deploying Controller
+# Released names carry no premium by default. To enable a decaying premium, e.g. starting at
+# 100 SEI and halving every day for 21 days, set "expiry_premium" to:
+#   { "start_price": 100000000, "duration": 1814400, "decay": { "exponential": { "half_life": 86400 } } }
 printf "Deploying Controller %s\n"
 addrController=$(seid tx wasm instantiate $codeController '{
+  "max_commitment_age": 86400,
+  "min_commitment_age": 60,
   "min_registration_duration": 31536000,
-  "tier1_price": 1000000,
-  "tier2_price": 300000,
-  "tier3_price": 100000,
+  "max_registration_duration": null,
+  "max_expiry_duration": null,
+  "duration_discounts": [],
+  "name_policy": null,
+  "price_tiers": [
+    { "min_length": 3, "max_length": 3, "annual_price": 1000000 },
+    { "min_length": 4, "max_length": 4, "annual_price": 300000 },
+    { "min_length": 5, "max_length": null, "annual_price": 100000 }
+  ],
   "whitelist_price": 500000,
+  "price_source": {
+    "sei_oracle": {
+      "max_staleness_blocks": 100
+    }
+  },
+  "accepted_denoms": [],
+  "expiry_premium": null,
+  "fee_split": null,
   "referal_percentage": [
     10,
     30
   ],
   "enable_registration": true,
-  "registrar_address": "'"$addrRegistrar"'", "reverse_registrar_address": "'"$addrReverse"'","description" : "DotSei Domains- Making your complex blockchain addresses easy"
+  "registrar_address": "'"$addrRegistrar"'", "reverse_registrar_address": "'"$addrReverse"'","description" : "DotSei Domains- Making your complex blockchain addresses easy",
+  "treasury": null
 }' --chain-id $CHAIN_ID --from $ACCOUNT_NAME --gas=4000000 --fees=2000000usei --broadcast-mode=block --label "dotnames-controller" --admin $ACCOUNT_ADDRESS --node $ENDPOINT -y| grep -A 1 -m 1 "key: _contract_address" | sed -n 's/.*value: //p' | xargs)
 printf "Deployed controller address is %s\n" $addrController
 printf  "Controller Address : $addrController \n" >> contracts.txt